@@ -4,16 +4,12 @@ use std::{
     os::unix::{fs::OpenOptionsExt, io::AsRawFd},
 };
 
-use io_uring::{
-    platform::iouring::IoUringUserData,
-    uringio::{
-        operator::{fs::Read, nop::Nop},
-        submission::{entry::Sqe64, submitter::Submit},
-        uring::{
-            enter::UringEnter,
-            mode::{Iopoll, Sqpoll},
-            UringIo, UringMix,
-        },
+use io_uring::uringio::{
+    owned::{Driver, ReadOwned},
+    uring::{
+        enter::UringEnter,
+        mode::{Iopoll, Sqpoll},
+        UringIo, UringMix,
     },
 };
 
@@ -28,16 +24,15 @@ fn main() -> io::Result<()> {
 
     let file = File::open("README.md")?;
 
-    let mut dst = vec![0; 1024];
-    let mut read = Read::new(&file, &mut dst);
-    read.user_data = IoUringUserData::from(0x42);
+    let read = ReadOwned::new(file, Vec::with_capacity(1024));
     println!("Read: {:#?}", read);
 
     let (enter, mut submitter, mut collector) = uring.borrow();
 
-    if let Err(sqe) = submitter.push(read) {
+    let mut driver = Driver::new();
+    let Ok(mut ticket) = driver.push(&mut submitter, read) else {
         panic!("submission queue is full");
-    }
+    };
 
     loop {
         println!("rerun");
@@ -45,13 +40,18 @@ fn main() -> io::Result<()> {
         submitter.submit();
 
         collector.update();
-        if let Some(cqe) = collector.next() {
-            println!("== cqe ==: {:?}", cqe);
-            println!("== dst ==: {:#?}", str::from_utf8(&dst).unwrap());
-            break;
-        } else {
-            println!("flush collector");
-            collector.flush(enter, 1)?;
+        driver.dispatch(collector.by_ref());
+        match driver.take(ticket) {
+            Ok(done) => {
+                println!("== res ==: {:?}", done.result);
+                println!("== dst ==: {:#?}", str::from_utf8(&done.buf).unwrap());
+                break;
+            },
+            Err(pending) => {
+                ticket = pending;
+                println!("flush collector");
+                collector.flush(enter, 1)?;
+            },
         }
 
         // std::thread::sleep(std::time::Duration::from_millis(1500));
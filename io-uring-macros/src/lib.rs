@@ -0,0 +1,398 @@
+//! Procedural macros for the `io_uring` uringio operators.
+//!
+//! See [`macro@op`] for the declarative SQE mapping.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, spanned::Spanned, visit_mut::VisitMut, Attribute, Error, Expr, Fields,
+    GenericParam, Ident, ItemStruct, Lifetime, LitInt, Result, Type, Visibility,
+};
+
+/// Generate a uringio operator from a declarative field to SQE slot mapping.
+///
+/// ```ignore
+/// #[op(code = Read, entry = Sqe64, output = usize, init = raw)]
+/// pub struct Read<'fd, 'dst> {
+///     #[sqe(fd)]
+///     pub fd: RawFd,
+///     #[sqe(off, default = 0, set)]
+///     pub offset: u64,
+///     #[sqe(addr)]
+///     pub ptr: IoUringPtr,
+///     #[sqe(offset = 48, size = 16, default = Default::default())]
+///     pub pi_attr: IoUringPiAttr,
+/// }
+/// ```
+///
+/// `opcode`, `flags` and `user_data` are always generated, gaps are filled with `_unusedN_`
+/// padding and lifetimes are carried by a `_marker_` field.
+///
+/// Op arguments:
+/// * `code`: `IoUringOp` variant
+/// * `entry`: `Sqe64` or `Sqe128`
+/// * `output`: `OpOutput` decoded from the CQE
+/// * `init`: name of the generated constructor, `init` by default
+///
+/// Field arguments:
+/// * slot: `ioprio`, `fd`, `off`, `addr2`, `addr`, `len`, `op_flags`, `buf_index`,
+///   `buf_group`, `personality`, `file_index`, `splice_fd_in`, `addr3`, `cmd`, or a raw
+///   `offset = N, size = N`
+/// * `default = expr`: initial value, otherwise taken by the constructor
+/// * `set`: generate a `set_<field>` setter
+/// * `set = personality`: the setter takes a `&Personality` and stores its id
+///
+/// Size, alignment and every offset are asserted at compile time, named slots are also
+/// checked against `io_uring_sqe`.
+#[proc_macro_attribute]
+pub fn op(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut op_args = OpArgs::default();
+    let parser = syn::meta::parser(|meta| op_args.parse(meta));
+    parse_macro_input!(args with parser);
+
+    let item = parse_macro_input!(input as ItemStruct);
+    expand(op_args, item).unwrap_or_else(Error::into_compile_error).into()
+}
+
+#[derive(Default)]
+struct OpArgs {
+    code: Option<Ident>,
+    entry: Option<Ident>,
+    output: Option<Type>,
+    init: Option<Ident>,
+}
+
+impl OpArgs {
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta<'_>) -> Result<()> {
+        if meta.path.is_ident("code") {
+            self.code = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("entry") {
+            self.entry = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("output") {
+            self.output = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("init") {
+            self.init = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("expected `code`, `entry`, `output` or `init`"));
+        }
+        Ok(())
+    }
+}
+
+/// Slot: SQE byte range, with the `io_uring_sqe` field at the same offset
+#[derive(Clone, Copy)]
+struct Slot {
+    offset: usize,
+    size: usize,
+    sqe_field: Option<&'static str>,
+}
+
+impl Slot {
+    const fn named(offset: usize, size: usize, sqe_field: &'static str) -> Self {
+        Self { offset, size, sqe_field: Some(sqe_field) }
+    }
+
+    fn from_name(name: &Ident, entry_size: usize) -> Result<Self> {
+        let slot = match name.to_string().as_str() {
+            "ioprio" => Self::named(2, 2, "ioprio"),
+            "fd" => Self::named(4, 4, "fd"),
+            "off" | "addr2" => Self::named(8, 8, "off_or_addr2"),
+            "addr" => Self::named(16, 8, "addr_or_splice_off_in"),
+            "len" => Self::named(24, 4, "len"),
+            "op_flags" => Self::named(28, 4, "op_flags"),
+            "buf_index" | "buf_group" => Self::named(40, 2, "buf"),
+            "personality" => Self::named(42, 2, "personality"),
+            "file_index" | "splice_fd_in" => {
+                Self::named(44, 4, "splice_fd_in_or_file_index_or_addr_len")
+            },
+            "addr3" => Self::named(48, 8, "addr3_or_cmd"),
+            "cmd" => Self::named(48, entry_size - 48, "addr3_or_cmd"),
+            _ => return Err(Error::new(name.span(), "unknown SQE slot")),
+        };
+        Ok(slot)
+    }
+}
+
+struct OpField {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+    ty: Type,
+    slot: Slot,
+    default: Option<TokenStream2>,
+    set: Option<Setter>,
+}
+
+/// Setter: how `set_<field>` takes its value
+#[derive(Clone, Copy)]
+enum Setter {
+    Value,
+    Personality,
+}
+
+fn parse_sqe_attr(
+    attr: &Attribute,
+    entry_size: usize,
+) -> Result<(Slot, Option<Expr>, Option<Setter>)> {
+    let mut slot = None;
+    let mut offset = None;
+    let mut size = None;
+    let mut default = None;
+    let mut set = None;
+
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("offset") {
+            offset = Some(meta.value()?.parse::<LitInt>()?.base10_parse::<usize>()?);
+        } else if meta.path.is_ident("size") {
+            size = Some(meta.value()?.parse::<LitInt>()?.base10_parse::<usize>()?);
+        } else if meta.path.is_ident("default") {
+            default = Some(meta.value()?.parse::<Expr>()?);
+        } else if meta.path.is_ident("set") {
+            set = Some(Setter::Value);
+            if meta.input.peek(syn::Token![=]) {
+                let kind: Ident = meta.value()?.parse()?;
+                if kind != "personality" {
+                    return Err(Error::new(kind.span(), "expected `personality`"));
+                }
+                set = Some(Setter::Personality);
+            }
+        } else if let Some(name) = meta.path.get_ident() {
+            slot = Some(Slot::from_name(name, entry_size)?);
+        } else {
+            return Err(meta.error("expected SQE slot"));
+        }
+        Ok(())
+    })?;
+
+    let slot = match (slot, offset, size) {
+        (Some(slot), None, None) => slot,
+        (None, Some(offset), Some(size)) => Slot { offset, size, sqe_field: None },
+        _ => return Err(Error::new(attr.span(), "expected a named slot or `offset` and `size`")),
+    };
+
+    Ok((slot, default, set))
+}
+
+/// Replace every lifetime with `'static` for the const assertions
+struct StaticLifetime;
+
+impl VisitMut for StaticLifetime {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        *lifetime = Lifetime::new("'static", lifetime.span());
+    }
+}
+
+fn expand(args: OpArgs, item: ItemStruct) -> Result<TokenStream2> {
+    let span = Span::call_site();
+    let code = args.code.ok_or_else(|| Error::new(span, "missing `code`"))?;
+    let entry = args.entry.ok_or_else(|| Error::new(span, "missing `entry`"))?;
+    let output = args.output.ok_or_else(|| Error::new(span, "missing `output`"))?;
+    let init = args.init.unwrap_or_else(|| format_ident!("init"));
+
+    let entry_size = match entry.to_string().as_str() {
+        "Sqe64" => 64,
+        "Sqe128" => 128,
+        _ => return Err(Error::new(entry.span(), "expected `Sqe64` or `Sqe128`")),
+    };
+
+    if let Some(param) =
+        item.generics.params.iter().find(|p| !matches!(p, GenericParam::Lifetime(_)))
+    {
+        return Err(Error::new(param.span(), "op only supports lifetime generics"));
+    }
+
+    let Fields::Named(named) = &item.fields else {
+        return Err(Error::new(item.span(), "op requires named fields"));
+    };
+
+    let mut fields = Vec::new();
+    for field in &named.named {
+        let (sqe, attrs): (Vec<_>, Vec<_>) =
+            field.attrs.iter().cloned().partition(|attr| attr.path().is_ident("sqe"));
+        let [sqe] = sqe.as_slice() else {
+            return Err(Error::new(field.span(), "expected exactly one `#[sqe(..)]`"));
+        };
+
+        let (slot, default, set) = parse_sqe_attr(sqe, entry_size)?;
+        fields.push(OpField {
+            attrs,
+            vis: field.vis.clone(),
+            ident: field.ident.clone().expect("named field"),
+            ty: field.ty.clone(),
+            slot,
+            default: default.map(|expr| quote!(#expr)),
+            set,
+        });
+    }
+
+    let krate = quote!(::io_uring);
+    let iouring = quote!(#krate::platform::iouring);
+
+    // builtin slots
+    let builtins = [
+        (format_ident!("opcode"), quote!(#iouring::IoUringOp), Slot::named(0, 1, "opcode")),
+        (format_ident!("flags"), quote!(#iouring::IoUringSqeFlags), Slot::named(1, 1, "flags")),
+        (
+            format_ident!("user_data"),
+            quote!(#iouring::IoUringUserData),
+            Slot::named(32, 8, "user_data"),
+        ),
+    ];
+
+    let mut layout: Vec<(Slot, TokenStream2)> = builtins
+        .iter()
+        .map(|(ident, ty, slot)| (*slot, quote!(pub #ident: #ty)))
+        .chain(fields.iter().map(|f| {
+            let OpField { attrs, vis, ident, ty, .. } = f;
+            (f.slot, quote!(#(#attrs)* #vis #ident: #ty))
+        }))
+        .collect();
+    layout.sort_by_key(|(slot, _)| slot.offset);
+
+    let mut decls = Vec::new();
+    let mut pads = Vec::new();
+    let mut cursor = 0;
+    for (slot, decl) in layout {
+        if slot.offset < cursor {
+            return Err(Error::new(span, format!("SQE slot overlaps at offset {}", slot.offset)));
+        }
+        if slot.offset > cursor {
+            let pad = format_ident!("_unused{}_", pads.len());
+            let len = slot.offset - cursor;
+            decls.push(quote!(#pad: [u8; #len]));
+            pads.push((pad, len));
+        }
+        decls.push(decl);
+        cursor = slot.offset + slot.size;
+    }
+    if cursor > entry_size {
+        return Err(Error::new(span, "SQE slot out of entry"));
+    }
+    if cursor < entry_size {
+        let pad = format_ident!("_unused{}_", pads.len());
+        let len = entry_size - cursor;
+        decls.push(quote!(#pad: [u8; #len]));
+        pads.push((pad, len));
+    }
+
+    let lifetimes: Vec<_> = item.generics.lifetimes().map(|param| &param.lifetime).collect();
+    let (marker_decl, marker_init) = if lifetimes.is_empty() {
+        (quote!(), quote!())
+    } else {
+        (
+            quote!(_marker_: ::core::marker::PhantomData<(#(&#lifetimes (),)*)>,),
+            quote!(_marker_: ::core::marker::PhantomData,),
+        )
+    };
+
+    let ItemStruct { attrs, vis, ident, generics, .. } = &item;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let init_args = fields.iter().filter(|f| f.default.is_none()).map(|f| {
+        let OpField { ident, ty, .. } = f;
+        quote!(#ident: #ty)
+    });
+    let init_fields = fields.iter().map(|f| match &f.default {
+        Some(default) => {
+            let ident = &f.ident;
+            quote!(#ident: #default)
+        },
+        None => {
+            let ident = &f.ident;
+            quote!(#ident)
+        },
+    });
+    let init_pads = pads.iter().map(|(pad, len)| quote!(#pad: [0; #len]));
+
+    let setters = fields.iter().filter_map(|f| {
+        let OpField { ident, ty, .. } = f;
+        let setter = format_ident!("set_{}", ident);
+        let setter = match f.set? {
+            Setter::Value => quote! {
+                pub fn #setter(mut self, #ident: #ty) -> Self {
+                    self.#ident = #ident;
+                    self
+                }
+            },
+            Setter::Personality => quote! {
+                pub fn #setter(
+                    mut self,
+                    #ident: &#krate::uringio::register::personality::Personality<'_>,
+                ) -> Self {
+                    self.#ident = #ident.id();
+                    self
+                }
+            },
+        };
+        Some(setter)
+    });
+
+    // const assertions on the 'static instance
+    let mut static_ty: Type = syn::parse2(quote!(#ident #ty_generics))?;
+    StaticLifetime.visit_type_mut(&mut static_ty);
+    let sqe = quote!(#iouring::IoUringSqe);
+    let entry_ty = quote!(#krate::uringio::submission::entry::#entry);
+
+    let builtin_asserts = builtins.iter().map(|(field, _, slot)| {
+        let offset = slot.offset;
+        quote!(::core::assert!(::core::mem::offset_of!(__Op, #field) == #offset);)
+    });
+    let field_asserts = fields.iter().map(|f| {
+        let OpField { ident: field, slot, .. } = f;
+        let Slot { offset, size, sqe_field } = *slot;
+        let mut ty = f.ty.clone();
+        StaticLifetime.visit_type_mut(&mut ty);
+        let sqe_assert = sqe_field.map(|name| {
+            let name = Ident::new(name, Span::call_site());
+            quote!(::core::assert!(::core::mem::offset_of!(#sqe, #name) == #offset);)
+        });
+        quote! {
+            ::core::assert!(::core::mem::offset_of!(__Op, #field) == #offset);
+            ::core::assert!(::core::mem::size_of::<#ty>() == #size);
+            #sqe_assert
+        }
+    });
+
+    Ok(quote! {
+        #(#attrs)*
+        #[derive(Debug)]
+        #[repr(C)]
+        #vis struct #ident #generics #where_clause {
+            #(#decls,)*
+            #marker_decl
+        }
+
+        impl #impl_generics #krate::uringio::operator::Op for #ident #ty_generics #where_clause {
+            type Entry = #entry_ty;
+
+            type Output = #output;
+
+            const OP_CODE: #iouring::IoUringOp = #iouring::IoUringOp::#code;
+        }
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #vis fn #init(#(#init_args),*) -> Self {
+                Self {
+                    opcode: <Self as #krate::uringio::operator::Op>::OP_CODE,
+                    flags: #iouring::IoUringSqeFlags::empty(),
+                    user_data: ::core::default::Default::default(),
+                    #(#init_fields,)*
+                    #(#init_pads,)*
+                    #marker_init
+                }
+            }
+
+            #(#setters)*
+        }
+
+        const _: () = {
+            type __Op = #static_ty;
+
+            ::core::assert!(::core::mem::size_of::<__Op>() == ::core::mem::size_of::<#entry_ty>());
+            ::core::assert!(::core::mem::align_of::<__Op>() == ::core::mem::align_of::<#entry_ty>());
+            #(#builtin_asserts)*
+            #(#field_asserts)*
+        };
+    })
+}
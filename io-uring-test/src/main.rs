@@ -75,13 +75,17 @@ fn test<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     tests::queue::test_debug_print(&mut ring, &test)?;
     tests::queue::test_msg_ring_data(&mut ring, &test)?;
     tests::queue::test_msg_ring_send_fd(&mut ring, &test)?;
+    tests::queue::test_msg_ring_channel(&mut ring, &test)?;
+    tests::queue::test_msg_ring_channel_reclaim(&mut ring, &test)?;
 
     tests::queue::test_batch(&mut ring, &test)?;
 
     // register
     tests::register::test_register_files_sparse(&mut ring, &test)?;
+    tests::register::test_register_napi(&mut ring, &test)?;
     tests::register_buffers::test_register_buffers(&mut ring, &test)?;
     tests::register_buffers::test_register_buffers_update(&mut ring, &test)?;
+    tests::register_buffers::test_register_clone_buffers(&mut ring, &test)?;
     tests::register_buf_ring::test_register_buf_ring(&mut ring, &test)?;
     tests::register_sync_cancel::test_register_sync_cancel(&mut ring, &test)?;
     tests::register_sync_cancel::test_register_sync_cancel_unsubmitted(&mut ring, &test)?;
@@ -183,6 +187,29 @@ fn test<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     // os (process)
     tests::os::test_waitid(&mut ring, &test)?;
 
+    // uringio
+    tests::uringio::test_resize(&test)?;
+    tests::uringio::test_register_ring_fds(&test)?;
+    tests::uringio::test_setup_registered(&test)?;
+    tests::uringio::test_tagged_buffers(&test)?;
+    tests::uringio::test_fixed_fd_install(&test)?;
+    tests::uringio::test_eventfd(&test)?;
+    tests::uringio::test_waitid(&test)?;
+    tests::uringio::test_epoll(&test)?;
+    #[cfg(feature = "mio")]
+    tests::uringio::test_eventfd_mio(&test)?;
+    tests::uringio::test_socket_cmd(&test)?;
+    tests::uringio::test_uring_cmd(&test)?;
+
+    // uringio runtime
+    #[cfg(feature = "runtime")]
+    {
+        tests::runtime::test_runtime_read_write(&test)?;
+        tests::runtime::test_runtime_remote_wake(&test)?;
+        tests::runtime::test_runtime_remote_wake_in_flight(&test)?;
+        tests::runtime::test_runtime_drop_cancel(&test)?;
+    }
+
     // regression test
     tests::regression::test_issue154(&mut ring, &test)?;
 
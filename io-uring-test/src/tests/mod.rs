@@ -12,6 +12,9 @@ pub mod register_buf_ring;
 pub mod register_buffers;
 pub mod register_sync_cancel;
 pub mod regression;
+#[cfg(feature = "runtime")]
+pub mod runtime;
 pub mod timeout;
+pub mod uringio;
 
 pub mod sqpoll;
@@ -304,3 +304,90 @@ pub fn test_msg_ring_send_fd<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
 
     Ok(())
 }
+
+pub fn test_msg_ring_channel<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    use io_uring::uringio::channel::channel;
+    use std::os::unix::io::{AsRawFd, BorrowedFd};
+
+    require!(
+        test;
+        test.probe.is_supported(opcode::MsgRingData::CODE);
+    );
+
+    println!("test msg_ring_channel");
+
+    // Values are parked in the channel slab, the destination ring only sees
+    // the channel key and a token, and never submits anything itself.
+    let mut dest_ring = IoUring::new(4)?;
+    let dest_fd = unsafe { BorrowedFd::borrow_raw(dest_ring.as_raw_fd()) };
+    let (tx, rx) = channel::<String>(&dest_fd, 0x63_68_61_6e, 2)?;
+
+    tx.send_squeue(&mut ring.submission(), "hello".to_owned())
+        .expect("queue is full");
+    tx.send_squeue(&mut ring.submission(), "world".to_owned())
+        .expect("queue is full");
+
+    // Slab is bounded by the channel capacity
+    let value = tx
+        .send_squeue(&mut ring.submission(), "full".to_owned())
+        .unwrap_err();
+    assert_eq!(value, "full");
+
+    ring.submit()?;
+
+    // Successful messages skip the source CQE
+    let source_cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    assert!(source_cqes.is_empty());
+
+    dest_ring.submit_and_wait(2)?;
+    let values: Vec<String> = dest_ring
+        .completion()
+        .map(Into::into)
+        .filter_map(|cqe: cqueue::Entry| rx.recv_entry(&cqe))
+        .collect();
+    assert_eq!(values, ["hello", "world"]);
+
+    Ok(())
+}
+
+pub fn test_msg_ring_channel_reclaim<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    use io_uring::uringio::channel::channel;
+    use std::os::unix::io::{AsRawFd, BorrowedFd};
+
+    // Messages to R_DISABLED rings fail since kernel 6.3, WaitId (6.7) is the
+    // closest opcode.
+    require!(
+        test;
+        test.probe.is_supported(opcode::WaitId::CODE);
+    );
+
+    println!("test msg_ring_channel_reclaim");
+
+    // A disabled ring rejects the message, the source CQE hands the value back
+    let disabled_ring: IoUring<S, C> = IoUring::builder().setup_r_disabled().build(4)?;
+    let disabled_fd = unsafe { BorrowedFd::borrow_raw(disabled_ring.as_raw_fd()) };
+    let (tx, _rx) = channel::<String>(&disabled_fd, 0x63_68_61_6e, 1)?;
+
+    tx.send_squeue(&mut ring.submission(), "lost".to_owned())
+        .expect("queue is full");
+    ring.submit_and_wait(1)?;
+
+    let cqe: cqueue::Entry = ring.completion().next().expect("cqueue is empty").into();
+    assert_eq!(cqe.result(), -libc::EBADFD);
+    assert_eq!(tx.reclaim_entry(&cqe).as_deref(), Some("lost"));
+
+    // The slot is free again
+    tx.send_squeue(&mut ring.submission(), "again".to_owned())
+        .expect("queue is full");
+    ring.submit_and_wait(1)?;
+    let cqe: cqueue::Entry = ring.completion().next().expect("cqueue is empty").into();
+    assert_eq!(tx.reclaim_entry(&cqe).as_deref(), Some("again"));
+
+    Ok(())
+}
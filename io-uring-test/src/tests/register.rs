@@ -1,4 +1,5 @@
 use crate::Test;
+use io_uring::register::{Napi, NapiTracking};
 use io_uring::{cqueue, opcode, squeue, IoUring};
 
 pub fn test_register_files_sparse<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
@@ -68,3 +69,40 @@ pub fn test_register_files_sparse<S: squeue::EntryMarker, C: cqueue::EntryMarker
 
     Ok(())
 }
+
+pub fn test_register_napi<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    // NAPI registration was introduced in kernel 6.9, as was the opcode for Ftruncate.
+    require!(
+        test;
+        test.probe.is_supported(opcode::Ftruncate::CODE);
+    );
+
+    println!("test register_napi");
+
+    // Busy poll a loopback socket, loopback has no NAPI context so this only checks settings.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let _stream = std::net::TcpStream::connect(listener.local_addr()?)?;
+
+    let timeout = std::time::Duration::from_micros(100);
+    let mut napi = Napi::new()
+        .busy_poll_timeout(timeout)
+        .prefer_busy_poll(true)
+        .tracking(NapiTracking::Dynamic);
+    ring.submitter().register_napi(&mut napi)?;
+
+    // Registering again reads back the settings of the previous registration.
+    let mut prev = Napi::new();
+    ring.submitter().register_napi(&mut prev)?;
+    assert_eq!(prev.get_busy_poll_timeout(), timeout);
+    assert!(prev.get_prefer_busy_poll());
+    assert_eq!(prev.get_tracking(), NapiTracking::Dynamic);
+
+    let prev = ring.submitter().unregister_napi()?;
+    assert_eq!(prev.get_busy_poll_timeout(), std::time::Duration::ZERO);
+    assert!(!prev.get_prefer_busy_poll());
+
+    Ok(())
+}
@@ -9,7 +9,7 @@ use io_uring::{cqueue, opcode, squeue, IoUring};
 use std::cell::Cell;
 use std::fmt;
 use std::io;
-use std::os::unix::io::AsRawFd;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
 use std::ptr;
 use std::rc::Rc;
 use std::sync::atomic::{self, AtomicU16};
@@ -257,6 +257,17 @@ impl InnerBufRing {
         ring.submitter().unregister_buf_ring(bgid)
     }
 
+    // Returns the kernel head and the tail of the buf_ring.
+    pub(crate) fn status<S, C>(&self, ring: &mut IoUring<S, C>) -> io::Result<types::PbufStatus>
+    where
+        S: squeue::EntryMarker,
+        C: cqueue::EntryMarker,
+    {
+        let ring_base = self.ring_start.as_ptr() as *const BufRingEntry;
+        // Safety: ring_start is the buf_ring registered as bgid.
+        unsafe { ring.submitter().buf_ring_status(self.bgid, ring_base) }
+    }
+
     // Returns the buffer group id.
     fn bgid(&self) -> Bgid {
         self.bgid
@@ -712,6 +723,82 @@ fn buf_ring_play<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     Ok(())
 }
 
+// Verify the kernel head reported for a buf_ring by reading from a pipe until the ring is
+// exhausted, then returning buffers one by one.
+fn buf_ring_status<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    _test: &Test,
+) -> io::Result<()> {
+    let text = b"ENOBUFS";
+    let len = text.len() as u32;
+
+    let buf_ring = Builder::new(999)
+        .ring_entries(4)
+        .buf_cnt(2)
+        .buf_len(128)
+        .build()?;
+    buf_ring.rc.register(ring)?;
+
+    let status = |ring: &mut IoUring<S, C>, available, outstanding| {
+        let status = buf_ring.rc.status(ring).unwrap();
+        assert_eq!(status.available(), available);
+        assert_eq!(status.outstanding(buf_ring.rc.buf_cnt), outstanding);
+    };
+    status(ring, 2, 0);
+
+    let mut fds = [0; 2];
+    assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+    let (rx, tx) = unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+    let rx = types::Fd(rx.as_raw_fd());
+    let tx = types::Fd(tx.as_raw_fd());
+
+    // Pipes have no offset, read from the current position
+    let mut read = || -> io::Result<GBuf> {
+        write_text_to_file(ring, tx, text)?;
+
+        let read_e = opcode::Read::new(rx, ptr::null_mut(), len)
+            .offset(u64::MAX)
+            .buf_group(buf_ring.rc.bgid())
+            .build()
+            .user_data(0x03)
+            .flags(squeue::Flags::BUFFER_SELECT)
+            .into();
+        unsafe { ring.submission().push(&read_e).expect("queue is full") };
+        ring.submit_and_wait(1)?;
+
+        let cqe: cqueue::Entry = ring.completion().next().expect("cqueue is empty").into();
+        assert_eq!(cqe.user_data(), 0x03);
+        if cqe.result() < 0 {
+            return Err(io::Error::from_raw_os_error(-cqe.result()));
+        }
+        buf_ring
+            .rc
+            .get_buf(buf_ring.clone(), cqe.result() as _, cqe.flags())
+    };
+
+    let buf0 = read()?;
+    let buf1 = read()?;
+    assert_eq!(buf0.as_slice(), text);
+    assert_eq!(buf1.as_slice(), text);
+
+    // Exhausted, the failed read leaves its text in the pipe
+    let err = read().unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOBUFS));
+    status(ring, 0, 2);
+
+    drop(buf0);
+    status(ring, 1, 1);
+    drop(buf1);
+    status(ring, 2, 0);
+
+    buf_ring.rc.unregister(ring)?;
+
+    // Unregistered buffer groups have no status
+    assert!(ring.submitter().register_pbuf_status(999).is_err());
+
+    Ok(())
+}
+
 pub fn test_register_buf_ring<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     ring: &mut IoUring<S, C>,
     test: &Test,
@@ -733,5 +820,10 @@ pub fn test_register_buf_ring<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
 
     buf_ring_play(ring, test)?;
 
+    // register_pbuf_status was introduced in kernel 6.8, as was the opcode for FixedFdInstall.
+    if test.probe.is_supported(opcode::FixedFdInstall::CODE) {
+        buf_ring_status(ring, test)?;
+    }
+
     Ok(())
 }
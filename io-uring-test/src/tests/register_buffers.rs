@@ -2,6 +2,7 @@ use crate::Test;
 use io_uring::{
     cqueue,
     opcode::{ReadFixed, WriteFixed},
+    register::CloneBuffers,
     squeue,
     types::Fd,
     IoUring,
@@ -10,7 +11,7 @@ use libc::iovec;
 use std::{
     fs::File,
     io::{self, IoSliceMut},
-    io::{Error, Write},
+    io::{Error, Read, Seek, Write},
     ops::DerefMut,
     os::fd::AsRawFd,
     os::fd::FromRawFd,
@@ -321,6 +322,100 @@ pub fn test_register_buffers_update<S: squeue::EntryMarker, C: cqueue::EntryMark
     return Ok(());
 }
 
+pub fn test_register_clone_buffers<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    _ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    // Cloning was introduced in 6.12 and ranges in 6.13, ReadvFixed is the closest newer opcode.
+    require!(
+        test;
+        test.probe.is_supported(opcode::ReadvFixed::CODE);
+    );
+
+    println!("test register_clone_buffers");
+
+    const BUF_SIZE: usize = 1 << 12;
+
+    // Fresh rings, cloning requires an empty destination buffer table
+    let src = IoUring::<S, C>::builder().build(8)?;
+    let mut dst = IoUring::<S, C>::builder().build(8)?;
+
+    let mut bufs = [vec![b'A'; BUF_SIZE], vec![b'B'; BUF_SIZE]];
+    let iovecs = bufs
+        .iter_mut()
+        .map(|buf| iovec {
+            iov_base: buf.as_mut_ptr().cast(),
+            iov_len: buf.len(),
+        })
+        .collect::<Vec<_>>();
+
+    src.submitter().register_buffers_sparse(2)?;
+    // Safety: `bufs` outlive both rings
+    unsafe { src.submitter().register_buffers_update(0, &iovecs, None)? };
+
+    let src_fd = src.as_raw_fd();
+    // Safety: `bufs` outlive both rings
+    unsafe {
+        dst.submitter()
+            .register_clone_buffers(&CloneBuffers::new(src_fd))?;
+    }
+
+    // The destination table is in use now
+    let err = unsafe {
+        dst.submitter()
+            .register_clone_buffers(&CloneBuffers::new(src_fd))
+            .unwrap_err()
+    };
+    assert_eq!(err.raw_os_error(), Some(libc::EBUSY));
+
+    // Replace index 0 with buffer 1 of the source ring
+    let clone = CloneBuffers::new(src_fd)
+        .src_offset(1)
+        .dst_offset(0)
+        .count(1)
+        .replace(true);
+    // Safety: `bufs` outlive both rings
+    unsafe { dst.submitter().register_clone_buffers(&clone)? };
+
+    // Cloned buffers outlive the source registration
+    src.submitter().unregister_buffers()?;
+
+    let mut file = tempfile::tempfile()?;
+    let fd = Fd(file.as_raw_fd());
+    let write = |buf: &[u8], index: u16| -> S {
+        WriteFixed::new(fd, buf.as_ptr(), BUF_SIZE as _, index)
+            .build()
+            .user_data(index as _)
+            .into()
+    };
+
+    // Index 0 holds buffer 1 now, buffer 0 is out of its range
+    unsafe {
+        let mut sq = dst.submission();
+        sq.push(&write(&bufs[0], 0)).unwrap();
+    }
+    dst.submit_and_wait(1)?;
+    let cqe: cqueue::Entry = dst.completion().next().unwrap().into();
+    assert_eq!(cqe.result(), -EFAULT);
+
+    unsafe {
+        let mut sq = dst.submission();
+        sq.push(&write(&bufs[1], 0)).unwrap();
+    }
+    dst.submit_and_wait(1)?;
+    let cqe: cqueue::Entry = dst.completion().next().unwrap().into();
+    assert_eq!(cqe.result(), BUF_SIZE as i32);
+
+    let mut data = Vec::new();
+    file.rewind()?;
+    file.read_to_end(&mut data)?;
+    assert_eq!(data, bufs[1]);
+
+    dst.submitter().unregister_buffers()?;
+
+    Ok(())
+}
+
 /// Create a pipe and return both ends as RAII `File` handles
 fn create_pipe() -> io::Result<(File, File)> {
     let mut fds = [-1, -1];
@@ -0,0 +1,205 @@
+use crate::tests::uringio::uringio_supported;
+use crate::Test;
+use io_uring::uringio::completion::entry::Cqe16;
+use io_uring::uringio::owned::{ReadOwned, WriteOwned};
+use io_uring::uringio::runtime::Runtime;
+use io_uring::uringio::submission::entry::Sqe64;
+use io_uring::uringio::uring::mode::Sqpoll;
+use io_uring::uringio::uring::UringIo;
+use std::fs::File;
+use std::future::Future;
+use std::io::Write;
+use std::os::unix::io::{FromRawFd, OwnedFd};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::Duration;
+
+fn pipe() -> anyhow::Result<(OwnedFd, OwnedFd)> {
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    // Safety: both fds were just created by pipe2
+    unsafe { Ok((OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1]))) }
+}
+
+/// Ready once `done` is set, woken from another thread.
+struct Remote {
+    done: Arc<AtomicBool>,
+    waker: Arc<Mutex<Option<Waker>>>,
+}
+
+impl Future for Remote {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        *self.waker.lock().unwrap() = Some(cx.waker().clone());
+        if self.done.load(Ordering::Acquire) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+pub fn test_runtime_read_write(test: &Test) -> anyhow::Result<()> {
+    require!(
+        test;
+        uringio_supported(test);
+    );
+
+    println!("test runtime read_write");
+
+    let (fd, args) = Sqpoll::new::<Sqe64, Cqe16>(8).setup()?;
+    let uring = UringIo::new(&fd, &args)?;
+    let rt = Runtime::new(uring)?;
+    let (rx, tx) = pipe()?;
+
+    let handle = rt.handle().clone();
+    let reader = rt.spawn(async move {
+        let read = handle
+            .submit(ReadOwned::new(rx, Vec::with_capacity(16)))
+            .await;
+        (read.result.unwrap(), read.buf)
+    });
+
+    let handle = rt.handle().clone();
+    let (len, buf) = rt.block_on(async move {
+        let write = handle.submit(WriteOwned::new(tx, b"hello".to_vec())).await;
+        assert_eq!(write.result.unwrap(), 5);
+        reader.await
+    })?;
+    assert_eq!(len, 5);
+    assert_eq!(buf, b"hello");
+
+    Ok(())
+}
+
+pub fn test_runtime_remote_wake(test: &Test) -> anyhow::Result<()> {
+    require!(
+        test;
+        uringio_supported(test);
+    );
+
+    println!("test runtime remote_wake");
+
+    let (fd, args) = Sqpoll::new::<Sqe64, Cqe16>(8).setup()?;
+    let uring = UringIo::new(&fd, &args)?;
+    let rt = Runtime::new(uring)?;
+
+    // No op in flight, block_on parks until the other thread wakes it.
+    let done = Arc::new(AtomicBool::new(false));
+    let waker = Arc::new(Mutex::new(None::<Waker>));
+    let remote = {
+        let done = done.clone();
+        let waker = waker.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            done.store(true, Ordering::Release);
+            if let Some(waker) = waker.lock().unwrap().take() {
+                waker.wake();
+            }
+        })
+    };
+
+    rt.block_on(Remote { done, waker })?;
+    remote.join().unwrap();
+
+    Ok(())
+}
+
+pub fn test_runtime_remote_wake_in_flight(test: &Test) -> anyhow::Result<()> {
+    require!(
+        test;
+        uringio_supported(test);
+    );
+
+    println!("test runtime remote_wake_in_flight");
+
+    let (fd, args) = Sqpoll::new::<Sqe64, Cqe16>(8).setup()?;
+    let uring = UringIo::new(&fd, &args)?;
+    let rt = Runtime::new(uring)?;
+    let (rx, tx) = pipe()?;
+    let stall_tx = tx.try_clone()?;
+
+    let handle = rt.handle().clone();
+    let reader = rt.spawn(async move {
+        let read = handle
+            .submit(ReadOwned::new(rx, Vec::with_capacity(16)))
+            .await;
+        (read.result.unwrap(), read.buf)
+    });
+
+    // A pipe read is in flight, block_on waits in the ring until the other thread wakes it.
+    // Should the wake be lost, the thread ends the wait by feeding the read itself.
+    let done = Arc::new(AtomicBool::new(false));
+    let waker = Arc::new(Mutex::new(None::<Waker>));
+    let (woken_tx, woken_rx) = mpsc::channel::<()>();
+    let remote = {
+        let done = done.clone();
+        let waker = waker.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            done.store(true, Ordering::Release);
+            if let Some(waker) = waker.lock().unwrap().take() {
+                waker.wake();
+            }
+            if woken_rx.recv_timeout(Duration::from_secs(2)).is_err() {
+                let _ = File::from(stall_tx).write_all(b"stall");
+            }
+        })
+    };
+
+    let handle = rt.handle().clone();
+    let (len, buf) = rt.block_on(async move {
+        Remote { done, waker }.await;
+        let _ = woken_tx.send(());
+        let write = handle.submit(WriteOwned::new(tx, b"hello".to_vec())).await;
+        assert_eq!(write.result.unwrap(), 5);
+        reader.await
+    })?;
+    remote.join().unwrap();
+    assert_eq!(len, 5);
+    assert_eq!(buf, b"hello");
+
+    Ok(())
+}
+
+pub fn test_runtime_drop_cancel(test: &Test) -> anyhow::Result<()> {
+    require!(
+        test;
+        uringio_supported(test);
+    );
+
+    println!("test runtime drop_cancel");
+
+    let (fd, args) = Sqpoll::new::<Sqe64, Cqe16>(8).setup()?;
+    let uring = UringIo::new(&fd, &args)?;
+    let rt = Runtime::new(uring)?;
+    let (rx, tx) = pipe()?;
+    let rx2 = rx.try_clone()?;
+
+    let handle = rt.handle().clone();
+    let buf = rt.block_on(async move {
+        // Poll the read once, the pipe is empty, then drop it.
+        let mut read = Box::pin(handle.submit(ReadOwned::new(rx, Vec::with_capacity(16))));
+        let pending = std::future::poll_fn(|cx| Poll::Ready(read.as_mut().poll(cx).is_pending()));
+        assert!(pending.await);
+        drop(read);
+
+        // The cancelled read must not take the data of the next one.
+        let write = handle.submit(WriteOwned::new(tx, b"hello".to_vec())).await;
+        assert_eq!(write.result.unwrap(), 5);
+        let read = handle
+            .submit(ReadOwned::new(rx2, Vec::with_capacity(16)))
+            .await;
+        assert_eq!(read.result.unwrap(), 5);
+        read.buf
+    })?;
+    assert_eq!(buf, b"hello");
+
+    Ok(())
+}
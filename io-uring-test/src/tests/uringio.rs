@@ -0,0 +1,647 @@
+use crate::Test;
+use io_uring::opcode;
+use io_uring::platform::epoll::{EpollEvent, EpollEventExt, EpollFlags};
+use io_uring::platform::iouring::{
+    io_uring_enter, IoUringCqeFlags, IoUringEnterFlags, IoUringSetupFlags, IoUringUserData,
+};
+use io_uring::platform::process::{spawn_pidfd, WaitIdOptions};
+use io_uring::uringio::completion::entry::Cqe16;
+use io_uring::uringio::operator::epoll::{EpollCtl, EpollWait};
+use io_uring::uringio::operator::fd::FixedFdInstall;
+use io_uring::uringio::operator::msg_ring::MsgRingData;
+use io_uring::uringio::operator::net::SocketCmd;
+use io_uring::uringio::operator::nop::Nop;
+use io_uring::uringio::operator::process::{WaitId, WaitIdTarget, WaitInfo, WaitStatus};
+use io_uring::uringio::operator::uring_cmd::UringCmd;
+use io_uring::uringio::operator::Op;
+use io_uring::uringio::owned::{Driver, WaitIdOwned};
+use io_uring::uringio::register::eventfd::EventFd;
+use io_uring::uringio::register::files::FileTable;
+use io_uring::uringio::register::tags::{RsrcKind, TaggedBuffers};
+use io_uring::uringio::submission::entry::Sqe64;
+use io_uring::uringio::submission::submitter::Submit;
+use io_uring::uringio::uring::mode::{Iopoll, Sqpoll};
+use io_uring::uringio::uring::UringIo;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::net::{TcpListener, TcpStream};
+use std::os::unix::io::{AsRawFd, BorrowedFd, FromRawFd, OwnedFd};
+use std::process::Command;
+
+/// Rings set up by uringio, required by every test here.
+///
+/// The uringio setup flags require kernel 6.6, no opcode came with it so probe
+/// for Waitid, which came with 6.7.
+pub fn uringio_supported(test: &Test) -> bool {
+    test.probe.is_supported(opcode::WaitId::CODE)
+}
+
+fn nop(user_data: u64) -> Nop {
+    let mut nop = Nop::new();
+    nop.user_data = IoUringUserData::from(user_data);
+    nop
+}
+
+/// Push `op` to a Sqpoll ring and wait for its CQE, return its res.
+fn complete_one<T>(uring: &mut UringIo<'_, Sqpoll>, op: T) -> anyhow::Result<i32>
+where
+    T: Op + Into<Sqe64>,
+{
+    complete_with(uring, op, |cqe| cqe.res)
+}
+
+/// Push `op` to a Sqpoll ring and wait for its CQE, return it mapped by `f`.
+fn complete_with<T, R>(
+    uring: &mut UringIo<'_, Sqpoll>,
+    op: T,
+    f: impl FnOnce(&Cqe16) -> R,
+) -> anyhow::Result<R>
+where
+    T: Op + Into<Sqe64>,
+{
+    let (enter, mut submitter, mut collector) = uring.borrow();
+    assert!(submitter.push(op).is_ok(), "queue is full");
+    submitter.submit();
+    drop(submitter);
+
+    collector.flush(enter, 1)?;
+    collector.update();
+    let cqe = collector.next().expect("cqueue is empty");
+    Ok(f(cqe))
+}
+
+pub fn test_resize(test: &Test) -> anyhow::Result<()> {
+    // Ring resizing was introduced in kernel 6.13, require ReadvFixed (6.15) as
+    // the closest opcode.
+    require!(
+        test;
+        uringio_supported(test);
+        test.probe.is_supported(opcode::ReadvFixed::CODE);
+    );
+
+    println!("test uringio resize");
+
+    // Iopoll mode sets up DEFER_TASKRUN, which resizing requires.
+    let (fd, args) = Iopoll::new::<Sqe64, Cqe16>(4).setup()?;
+    let mut uring = UringIo::new(&fd, &args)?;
+    assert_eq!(args.sq_entries, 4);
+
+    {
+        let (enter, mut submitter, _) = uring.borrow();
+        for user_data in 0..4 {
+            submitter.push(nop(user_data)).expect("queue is full");
+        }
+        assert_eq!(submitter.submit(enter, 0)?, 4);
+        submitter.update_head();
+
+        // Two more are left pending in the submission queue.
+        for user_data in 4..6 {
+            submitter.push(nop(user_data)).expect("queue is full");
+        }
+    }
+
+    // Four CQEs unreaped and two SQEs pending are moved into the new rings.
+    let args = uring.resize(16, 32)?;
+    assert_eq!(args.sq_entries, 16);
+    assert_eq!(args.cq_entries, 32);
+    assert!(args.flags.contains(IoUringSetupFlags::IOPOLL));
+
+    let (enter, mut submitter, _) = uring.borrow();
+    assert_eq!(submitter.size(), 2);
+    assert_eq!(submitter.submit(enter, 0)?, 2);
+    drop(submitter);
+
+    let mut user_data: Vec<u64> = uring
+        .collector()
+        .map(|cqe| {
+            assert_eq!(cqe.res, 0);
+            cqe.user_data.u64_()
+        })
+        .collect();
+    user_data.sort_unstable();
+    assert_eq!(user_data, [0, 1, 2, 3, 4, 5]);
+
+    Ok(())
+}
+
+pub fn test_register_ring_fds(test: &Test) -> anyhow::Result<()> {
+    require!(
+        test;
+        uringio_supported(test);
+    );
+
+    println!("test uringio register_ring_fds");
+
+    let (fd, args) = Sqpoll::new::<Sqe64, Cqe16>(4).setup()?;
+    let mut uring = UringIo::new(&fd, &args)?;
+    let (target_fd, target_args) = Sqpoll::new::<Sqe64, Cqe16>(4).setup()?;
+    let mut target = UringIo::new(&target_fd, &target_args)?;
+
+    // Register the ring itself, then go back to the plain fd.
+    uring.enter.register_ring_fd()?;
+    assert!(uring.enter.is_ring_registered());
+    uring.enter.unregister_ring_fd()?;
+    assert!(!uring.enter.is_ring_registered());
+    assert!(uring.enter.unregister_ring_fd().is_err());
+
+    // Batches land at the given offset, or at free slots.
+    let idxs = uring.enter.register_ring_fds(&[&target_fd], Some(4))?;
+    assert_eq!(idxs, [4]);
+    let more = uring.enter.register_ring_fds(&[&target_fd, &fd], None)?;
+    assert_eq!(more.len(), 2);
+    assert!(!more.contains(&4));
+    assert_eq!(uring.enter.unregister_ring_fds(&more)?, 2);
+
+    // MsgRing targets the ring by fd, the target waits through its registered index.
+    {
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        let msg = MsgRingData::new(&target_fd, IoUringUserData::from(0x42), 7);
+        submitter.push(msg).expect("queue is full");
+        submitter.submit();
+        drop(submitter);
+
+        collector.flush(enter, 1)?;
+        collector.update();
+        let cqe = collector.next().expect("cqueue is empty");
+        assert_eq!(cqe.res, 0);
+    }
+
+    let flags = IoUringEnterFlags::GETEVENTS | IoUringEnterFlags::REGISTERED_RING;
+    // Safety: idxs[0] is a registered index of the target ring, entered with REGISTERED_RING.
+    unsafe { io_uring_enter(BorrowedFd::borrow_raw(idxs[0] as _), 0, 1, flags)? };
+
+    let cqes: Vec<(u64, i32)> = target
+        .collector()
+        .map(|cqe| (cqe.user_data.u64_(), cqe.res))
+        .collect();
+    assert_eq!(cqes, [(0x42, 7)]);
+
+    assert_eq!(uring.enter.unregister_ring_fds(&idxs)?, 1);
+
+    Ok(())
+}
+
+pub fn test_setup_registered(test: &Test) -> anyhow::Result<()> {
+    require!(
+        test;
+        uringio_supported(test);
+    );
+
+    println!("test uringio setup_registered");
+
+    // Ring memory is allocated by setup_registered, sized by user_mem_size.
+    let (fd, args) = Sqpoll::new::<Sqe64, Cqe16>(4).setup_registered()?;
+    let mut uring = UringIo::new_registered(&fd, &args)?;
+    assert!(uring.enter.is_registered_only());
+    assert!(uring.enter.is_ring_registered());
+    assert!(uring.enter.register_ring_fd().is_err());
+    assert!(uring.enter.unregister_ring_fd().is_err());
+    assert!(uring.resize(8, 0).is_err());
+
+    let (enter, mut submitter, mut collector) = uring.borrow();
+    for user_data in 0..2 {
+        submitter.push(nop(user_data)).expect("queue is full");
+    }
+    submitter.submit();
+    drop(submitter);
+
+    collector.flush(enter, 2)?;
+    collector.update();
+    let user_data: Vec<u64> = collector
+        .by_ref()
+        .map(|cqe| {
+            assert_eq!(cqe.res, 0);
+            cqe.user_data.u64_()
+        })
+        .collect();
+    assert_eq!(user_data, [0, 1]);
+
+    Ok(())
+}
+
+pub fn test_tagged_buffers(test: &Test) -> anyhow::Result<()> {
+    require!(
+        test;
+        uringio_supported(test);
+    );
+
+    println!("test uringio tagged_buffers");
+
+    let (fd, args) = Sqpoll::new::<Sqe64, Cqe16>(4).setup()?;
+    let mut uring = UringIo::new(&fd, &args)?;
+    let bufs = TaggedBuffers::register(&uring.enter, vec![vec![1u8; 16], vec![2u8; 16]])?;
+    assert_eq!(bufs.size(), 2);
+    assert_eq!(bufs.pending(), 2);
+
+    // Replace slot 0 and empty slot 1, both old buffers come back by their tag CQEs.
+    bufs.update(&uring.enter, 0, vec![3u8; 16])?;
+    bufs.remove(&uring.enter, 1)?;
+    assert!(bufs.update(&uring.enter, 2, vec![4u8; 16]).is_err());
+    assert_eq!(bufs.pending(), 3);
+
+    let mut released = Vec::new();
+    {
+        let (enter, _, mut collector) = uring.borrow();
+        collector.flush(enter, 2)?;
+        collector.update();
+        for cqe in collector.by_ref() {
+            let user_data = cqe.user_data.u64_();
+            assert_eq!(RsrcKind::from_user_data(user_data), Some(RsrcKind::Buffer));
+            let buf = bufs.release(user_data).expect("unknown tag");
+            assert_eq!(buf.kind, RsrcKind::Buffer);
+            released.push((buf.index, buf.resource));
+        }
+    }
+    released.sort_unstable();
+    assert_eq!(released, [(0, vec![1u8; 16]), (1, vec![2u8; 16])]);
+    assert_eq!(bufs.pending(), 1);
+
+    // Unregistering releases the installed buffer, nothing left to leak on drop.
+    bufs.unregister()?;
+    assert!(bufs.unregister().is_err());
+    {
+        let (enter, _, mut collector) = uring.borrow();
+        collector.flush(enter, 1)?;
+        collector.update();
+        let cqe = collector.next().expect("cqueue is empty");
+        let buf = bufs.release(cqe.user_data.u64_()).expect("unknown tag");
+        assert_eq!((buf.index, buf.resource), (0, vec![3u8; 16]));
+    }
+    assert_eq!(bufs.pending(), 0);
+
+    Ok(())
+}
+
+fn epoll_create() -> anyhow::Result<OwnedFd> {
+    let epfd = unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) };
+    if epfd < 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(unsafe { OwnedFd::from_raw_fd(epfd) })
+}
+
+pub fn test_eventfd(test: &Test) -> anyhow::Result<()> {
+    require!(
+        test;
+        uringio_supported(test);
+    );
+
+    println!("test uringio eventfd");
+
+    let (fd, args) = Sqpoll::new::<Sqe64, Cqe16>(4).setup()?;
+    let mut uring = UringIo::new(&fd, &args)?;
+    let eventfd = EventFd::new()?;
+    eventfd.register(&uring.enter)?;
+
+    let epfd = epoll_create()?;
+    eventfd.epoll_add(&epfd, 0x42)?;
+
+    let epoll_wait = |timeout: libc::c_int| {
+        let mut event = libc::epoll_event { events: 0, u64: 0 };
+        let n = unsafe { libc::epoll_wait(epfd.as_raw_fd(), &mut event, 1, timeout) };
+        (n, event.u64)
+    };
+
+    assert_eq!(eventfd.clear()?, 0);
+    assert_eq!(epoll_wait(0).0, 0);
+
+    // A posted CQE signals the eventfd, clearing it makes it quiet again.
+    assert_eq!(complete_one(&mut uring, nop(1))?, 0);
+    assert_eq!(epoll_wait(1000), (1, 0x42));
+    assert!(eventfd.clear()? >= 1);
+    assert_eq!(epoll_wait(0).0, 0);
+
+    // Nothing is signalled while notifications are disabled through the CQ ring flags.
+    uring.cq.set_eventfd_disabled(true);
+    assert!(uring.cq.eventfd_disabled());
+    assert_eq!(complete_one(&mut uring, nop(2))?, 0);
+    assert_eq!(eventfd.clear()?, 0);
+    uring.cq.set_eventfd_disabled(false);
+
+    // Once unregistered, completions leave the eventfd alone.
+    eventfd.epoll_del(&epfd)?;
+    eventfd.unregister(&uring.enter)?;
+    assert_eq!(complete_one(&mut uring, nop(3))?, 0);
+    assert_eq!(eventfd.clear()?, 0);
+
+    Ok(())
+}
+
+#[cfg(feature = "mio")]
+pub fn test_eventfd_mio(test: &Test) -> anyhow::Result<()> {
+    use mio::{Events, Interest, Poll, Token};
+    use std::time::Duration;
+
+    require!(
+        test;
+        uringio_supported(test);
+    );
+
+    println!("test uringio eventfd_mio");
+
+    let (fd, args) = Sqpoll::new::<Sqe64, Cqe16>(4).setup()?;
+    let mut uring = UringIo::new(&fd, &args)?;
+    let mut eventfd = EventFd::new()?;
+    eventfd.register(&uring.enter)?;
+
+    let mut poll = Poll::new()?;
+    let mut events = Events::with_capacity(4);
+    poll.registry()
+        .register(&mut eventfd, Token(7), Interest::READABLE)?;
+
+    assert_eq!(complete_one(&mut uring, nop(1))?, 0);
+    poll.poll(&mut events, Some(Duration::from_secs(1)))?;
+    let event = events.iter().next().expect("eventfd not readable");
+    assert_eq!(event.token(), Token(7));
+    assert!(event.is_readable());
+    assert!(eventfd.clear()? >= 1);
+
+    poll.registry().deregister(&mut eventfd)?;
+    eventfd.unregister(&uring.enter)?;
+
+    Ok(())
+}
+
+pub fn test_epoll(test: &Test) -> anyhow::Result<()> {
+    // EpollWait was introduced in kernel 6.15, EpollCtl long before.
+    require!(
+        test;
+        uringio_supported(test);
+        test.probe.is_supported(opcode::EpollWait::CODE);
+    );
+
+    println!("test uringio epoll");
+
+    let (fd, args) = Sqpoll::new::<Sqe64, Cqe16>(4).setup()?;
+    let mut uring = UringIo::new(&fd, &args)?;
+    let epfd = epoll_create()?;
+    let (mut rx, mut tx) = std::io::pipe()?;
+
+    let event = EpollEvent::new(EpollFlags::IN, 0x42);
+    assert_eq!(
+        complete_one(&mut uring, EpollCtl::add(&epfd, &rx, &event))?,
+        0
+    );
+    let res = complete_one(&mut uring, EpollCtl::add(&epfd, &rx, &event))?;
+    assert_eq!(res, -libc::EEXIST);
+
+    // The wait reports the pipe readable with the data it was added with.
+    tx.write_all(b"ping")?;
+    let mut events = [EpollEvent::new(EpollFlags::empty(), 0); 4];
+    let n = complete_one(&mut uring, EpollWait::new(&epfd, &mut events))?;
+    assert_eq!(n, 1);
+    assert!(events[0].flags().contains(EpollFlags::IN));
+    assert_eq!(events[0].data(), 0x42);
+
+    let mut buf = [0; 4];
+    rx.read_exact(&mut buf)?;
+    assert_eq!(&buf, b"ping");
+
+    // Modify then remove the interest, a second delete finds nothing.
+    let event = EpollEvent::new(EpollFlags::OUT, 0x43);
+    assert_eq!(
+        complete_one(&mut uring, EpollCtl::modify(&epfd, &rx, &event))?,
+        0
+    );
+    assert_eq!(complete_one(&mut uring, EpollCtl::delete(&epfd, &rx))?, 0);
+    let res = complete_one(&mut uring, EpollCtl::delete(&epfd, &rx))?;
+    assert_eq!(res, -libc::ENOENT);
+
+    Ok(())
+}
+
+pub fn test_fixed_fd_install(test: &Test) -> anyhow::Result<()> {
+    require!(
+        test;
+        uringio_supported(test);
+        test.probe.is_supported(opcode::FixedFdInstall::CODE);
+    );
+
+    println!("test uringio fixed_fd_install");
+
+    let (fd, args) = Sqpoll::new::<Sqe64, Cqe16>(4).setup()?;
+    let mut uring = UringIo::new(&fd, &args)?;
+    let table = FileTable::register_sparse(&uring.enter, 4)?;
+    table.set_alloc_range(&uring.enter, 2, 2)?;
+
+    let mut file = tempfile::tempfile()?;
+    file.write_all(b"hello")?;
+
+    // Slots reserved for the kernel allocator or already in use are refused.
+    assert!(table.install_at(&uring.enter, 2, &file).is_err());
+    assert!(table.install_at(&uring.enter, 4, &file).is_err());
+    let slot = table.install_at(&uring.enter, 1, &file)?;
+    assert!(table.install_at(&uring.enter, 1, &file).is_err());
+    let other = table.install(&uring.enter, &file)?;
+    assert_eq!(other.index(), 0);
+    assert!(table.install(&uring.enter, &file).is_err());
+
+    let res = complete_one(&mut uring, FixedFdInstall::new(&slot))?;
+    assert!(res >= 0, "FixedFdInstall failed: {}", res);
+    let mut installed = unsafe { File::from_raw_fd(res) };
+
+    // The installed fd refers to the same open file, close-on-exec by default.
+    let fd_flags = unsafe { libc::fcntl(res, libc::F_GETFD) };
+    assert_eq!(fd_flags & libc::FD_CLOEXEC, libc::FD_CLOEXEC);
+    let mut buf = String::new();
+    installed.seek(SeekFrom::Start(0))?;
+    installed.read_to_string(&mut buf)?;
+    assert_eq!(buf, "hello");
+
+    // Removing gives the slot back, the installed fd outlives it.
+    table.remove(&uring.enter, slot)?;
+    table.remove(&uring.enter, other)?;
+    let slot = table.install_at(&uring.enter, 1, &file)?;
+    table.remove(&uring.enter, slot)?;
+    installed.write_all(b" world")?;
+
+    table.unregister(&uring.enter)?;
+
+    Ok(())
+}
+
+/// Fork a child exiting with `code`, or waiting for a signal if `None`.
+fn fork_child(code: Option<i32>) -> anyhow::Result<libc::pid_t> {
+    match unsafe { libc::fork() } {
+        -1 => Err(std::io::Error::last_os_error().into()),
+        // Only async-signal-safe calls in the child.
+        0 => unsafe {
+            if let Some(code) = code {
+                libc::_exit(code);
+            }
+            loop {
+                libc::pause();
+            }
+        },
+        pid => Ok(pid),
+    }
+}
+
+pub fn test_waitid(test: &Test) -> anyhow::Result<()> {
+    require!(
+        test;
+        uringio_supported(test);
+        test.probe.is_supported(opcode::WaitId::CODE);
+    );
+
+    println!("test uringio waitid");
+
+    let (fd, args) = Sqpoll::new::<Sqe64, Cqe16>(4).setup()?;
+    let mut uring = UringIo::new(&fd, &args)?;
+
+    // The exit status is decoded from the siginfo filled by the kernel.
+    let pid = fork_child(Some(7))?;
+    let mut info = WaitInfo::new();
+    let waitid = WaitId::new(WaitIdTarget::Pid(pid), &mut info);
+    assert_eq!(complete_one(&mut uring, waitid)?, 0);
+    assert_eq!(info.pid(), pid);
+    assert_eq!(info.status(), Some(WaitStatus::Exited(7)));
+
+    // A running child is left alone by WNOHANG, then reaped once killed.
+    let pid = fork_child(None)?;
+    let mut info = WaitInfo::new();
+    let waitid = WaitId::new(WaitIdTarget::Pid(pid), &mut info)
+        .set_options(WaitIdOptions::EXITED | WaitIdOptions::NOHANG);
+    assert_eq!(complete_one(&mut uring, waitid)?, 0);
+    assert_eq!(info.pid(), 0);
+    assert_eq!(info.status(), None);
+
+    assert_eq!(unsafe { libc::kill(pid, libc::SIGKILL) }, 0);
+    let mut info = WaitInfo::new();
+    let waitid = WaitId::new(WaitIdTarget::Pid(pid), &mut info);
+    assert_eq!(complete_one(&mut uring, waitid)?, 0);
+    assert_eq!(info.pid(), pid);
+    assert_eq!(info.status(), Some(WaitStatus::Killed(libc::SIGKILL)));
+
+    // The owned variant keeps the siginfo and the pidfd of a spawned child until completion.
+    let (child, pidfd) = spawn_pidfd(Command::new("sh").args(["-c", "exit 3"]))?;
+    let mut driver = Driver::new();
+    let ticket = {
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        let ticket = driver
+            .push(&mut submitter, WaitIdOwned::pidfd(pidfd))
+            .expect("queue is full");
+        submitter.submit();
+        drop(submitter);
+
+        collector.flush(enter, 1)?;
+        collector.update();
+        assert_eq!(driver.dispatch(collector.by_ref()), 1);
+        ticket
+    };
+    let done = driver.take(ticket).expect("waitid in flight");
+    done.result?;
+    assert!(done.fd.is_some());
+    assert_eq!(done.buf.pid(), child.id() as libc::pid_t);
+    assert_eq!(done.buf.status(), Some(WaitStatus::Exited(3)));
+
+    Ok(())
+}
+
+pub fn test_socket_cmd(test: &Test) -> anyhow::Result<()> {
+    // Socket commands were introduced in kernel 6.7, as was Waitid.
+    require!(
+        test;
+        uringio_supported(test);
+        test.probe.is_supported(opcode::WaitId::CODE);
+    );
+
+    println!("test uringio socket_cmd");
+
+    let (fd, args) = Sqpoll::new::<Sqe64, Cqe16>(4).setup()?;
+    let mut uring = UringIo::new(&fd, &args)?;
+
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let mut client = TcpStream::connect(listener.local_addr()?)?;
+    let (server, _) = listener.accept()?;
+
+    client.write_all(b"hello")?;
+    assert_eq!(complete_one(&mut uring, SocketCmd::siocinq(&server))?, 5);
+    assert_eq!(complete_one(&mut uring, SocketCmd::siocoutq(&server))?, 0);
+
+    // Set through the ring, read back through the ring and the socket.
+    let on: libc::c_int = 1;
+    let set = SocketCmd::setsockopt(&server, libc::SOL_SOCKET, libc::SO_KEEPALIVE, &on);
+    assert_eq!(complete_one(&mut uring, set)?, 0);
+
+    let mut keepalive: libc::c_int = 0;
+    let get = SocketCmd::getsockopt(
+        &server,
+        libc::SOL_SOCKET,
+        libc::SO_KEEPALIVE,
+        &mut keepalive,
+    );
+    let optlen = complete_one(&mut uring, get)?;
+    assert_eq!(optlen as usize, std::mem::size_of::<libc::c_int>());
+    assert_eq!(keepalive, 1);
+    assert!(socket2::SockRef::from(&server).keepalive()?);
+
+    Ok(())
+}
+
+/// Test command carrying two words, unknown to every driver.
+struct NopCmd {
+    data: [u64; 2],
+}
+
+impl UringCmd for NopCmd {
+    type Entry = Sqe64;
+    type Output = u32;
+
+    const CMD_OP: u32 = 0x42;
+
+    fn encode(&self, payload: &mut [u8; 16]) {
+        payload[..8].copy_from_slice(&self.data[0].to_ne_bytes());
+        payload[8..].copy_from_slice(&self.data[1].to_ne_bytes());
+    }
+
+    fn decode(res: u32, _: IoUringCqeFlags, _: &[u64; 2]) -> std::io::Result<Self::Output> {
+        Ok(res)
+    }
+}
+
+/// `SOCKET_URING_OP_SIOCINQ` as a driver command, decoded to the queued bytes.
+struct SiocInq;
+
+impl UringCmd for SiocInq {
+    type Entry = Sqe64;
+    type Output = usize;
+
+    const CMD_OP: u32 = 0;
+
+    fn encode(&self, _: &mut [u8; 16]) {}
+
+    fn decode(res: u32, _: IoUringCqeFlags, _: &[u64; 2]) -> std::io::Result<Self::Output> {
+        Ok(res as usize)
+    }
+}
+
+pub fn test_uring_cmd(test: &Test) -> anyhow::Result<()> {
+    require!(
+        test;
+        uringio_supported(test);
+        test.probe.is_supported(opcode::UringCmd16::CODE);
+    );
+
+    println!("test uringio uring_cmd");
+
+    let (fd, args) = Sqpoll::new::<Sqe64, Cqe16>(4).setup()?;
+    let mut uring = UringIo::new(&fd, &args)?;
+
+    // Regular files have no command handler, the error is decoded from the CQE.
+    let file = tempfile::tempfile()?;
+    let cmd = NopCmd { data: [1, 2] };
+    let err = complete_with(&mut uring, cmd.build(&file), NopCmd::decode_cqe16)?.unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EOPNOTSUPP));
+
+    // Sockets handle it, the result round-trips through decode.
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let mut client = TcpStream::connect(listener.local_addr()?)?;
+    let (server, _) = listener.accept()?;
+    client.write_all(b"hello")?;
+
+    let queued = complete_with(&mut uring, SiocInq.build(&server), SiocInq::decode_cqe16)??;
+    assert_eq!(queued, 5);
+
+    Ok(())
+}
@@ -47,6 +47,9 @@
 //!
 //! This approach allows you to provide your own bindings without relying on bindgen or the prebuilt bindings.
 
+// `::io_uring` paths generated by io-uring-macros
+extern crate self as io_uring;
+
 #[macro_use]
 mod util;
 pub mod cqueue;
@@ -1,3 +1,5 @@
+pub mod epoll;
 pub mod io;
 pub mod iouring;
 pub mod mmap;
+pub mod process;
@@ -0,0 +1,84 @@
+use std::io::{Error, Result};
+
+use bitflags::bitflags;
+pub use libc::epoll_event as EpollEvent;
+use rustix::fd::{AsFd, AsRawFd};
+
+/// EpollCtlOp: `EPOLL_CTL_*`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum EpollCtlOp {
+    // EPOLL_CTL_ADD
+    Add = libc::EPOLL_CTL_ADD as u32,
+
+    // EPOLL_CTL_DEL
+    Del = libc::EPOLL_CTL_DEL as u32,
+
+    // EPOLL_CTL_MOD
+    Mod = libc::EPOLL_CTL_MOD as u32,
+}
+
+bitflags! {
+    /// `EPOLL*` event flags
+    #[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+    #[repr(transparent)]
+    pub struct EpollFlags: u32 {
+        const IN = libc::EPOLLIN as u32;
+        const PRI = libc::EPOLLPRI as u32;
+        const OUT = libc::EPOLLOUT as u32;
+        const ERR = libc::EPOLLERR as u32;
+        const HUP = libc::EPOLLHUP as u32;
+        const RDNORM = libc::EPOLLRDNORM as u32;
+        const RDBAND = libc::EPOLLRDBAND as u32;
+        const WRNORM = libc::EPOLLWRNORM as u32;
+        const WRBAND = libc::EPOLLWRBAND as u32;
+        const MSG = libc::EPOLLMSG as u32;
+        const RDHUP = libc::EPOLLRDHUP as u32;
+        const EXCLUSIVE = libc::EPOLLEXCLUSIVE as u32;
+        const WAKEUP = libc::EPOLLWAKEUP as u32;
+        const ONESHOT = libc::EPOLLONESHOT as u32;
+        const ET = libc::EPOLLET as u32;
+    }
+}
+
+/// Typed accessors for `epoll_event`
+pub trait EpollEventExt {
+    fn new(flags: EpollFlags, data: u64) -> Self;
+
+    fn flags(&self) -> EpollFlags;
+
+    fn data(&self) -> u64;
+}
+
+impl EpollEventExt for EpollEvent {
+    #[inline]
+    fn new(flags: EpollFlags, data: u64) -> Self {
+        Self { events: flags.bits(), u64: data }
+    }
+
+    #[inline]
+    fn flags(&self) -> EpollFlags {
+        EpollFlags::from_bits_retain(self.events)
+    }
+
+    #[inline]
+    fn data(&self) -> u64 {
+        self.u64
+    }
+}
+
+/// `epoll_ctl(2)`, `event` is ignored by [`EpollCtlOp::Del`]
+pub fn epoll_ctl<Ep, Fd>(epfd: &Ep, op: EpollCtlOp, fd: &Fd, event: EpollEvent) -> Result<()>
+where
+    Ep: AsFd,
+    Fd: AsFd,
+{
+    let mut event = event;
+    let epfd = epfd.as_fd().as_raw_fd();
+    let fd = fd.as_fd().as_raw_fd();
+    // SAFETY: event valid during syscall
+    if unsafe { libc::epoll_ctl(epfd, op as _, fd, &mut event) } < 0 {
+        return Err(Error::last_os_error());
+    }
+    Ok(())
+}
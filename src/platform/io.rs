@@ -1 +1,25 @@
-pub use rustix::io::ReadWriteFlags;
+pub use libc::iovec as IoVec;
+pub use rustix::{
+    fd::{FromRawFd, OwnedFd},
+    io::ReadWriteFlags,
+};
+
+/// Create a nonblocking, close-on-exec `eventfd(2)` counter
+pub fn eventfd(init: u32) -> std::io::Result<OwnedFd> {
+    eventfd_with(init, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK)
+}
+
+/// Create a blocking, close-on-exec `eventfd(2)` counter, e.g. read in flight on a ring
+pub fn eventfd_blocking(init: u32) -> std::io::Result<OwnedFd> {
+    eventfd_with(init, libc::EFD_CLOEXEC)
+}
+
+fn eventfd_with(init: u32, flags: libc::c_int) -> std::io::Result<OwnedFd> {
+    // SAFETY: no pointer args
+    let fd = unsafe { libc::eventfd(init, flags) };
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // SAFETY: fd just created and owned
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
@@ -1,17 +1,28 @@
 pub use rustix::{
-    fd::{AsFd, AsRawFd, BorrowedFd, OwnedFd, RawFd},
+    fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
     ffi::c_void,
     io::Result,
     io_uring::{
-        io_uring_cqe as IoUringCqe, io_uring_enter, io_uring_params as IoUringParams,
-        io_uring_ptr as IoUringPtr, io_uring_register, io_uring_rsrc_update as IoUringRsrcUpdate,
-        io_uring_setup, io_uring_sqe as IoUringSqe, io_uring_user_data as IoUringUserData,
+        io_uring_buf as IoUringBuf, io_uring_cqe as IoUringCqe, io_uring_enter, io_uring_files_update as IoUringFilesUpdate,
+        io_uring_napi as IoUringNapi, io_uring_params as IoUringParams, io_uring_ptr as IoUringPtr,
+        io_uring_register, io_uring_register_with, io_uring_restriction as IoUringRestriction,
+        io_uring_rsrc_register as IoUringRsrcRegister, io_uring_rsrc_update as IoUringRsrcUpdate,
+        io_uring_rsrc_update2 as IoUringRsrcUpdate2, io_uring_setup, io_uring_sqe as IoUringSqe,
+        io_uring_user_data as IoUringUserData,
+        register_or_sqe_op_or_sqe_flags_union as IoUringRestrictionArg, ClockId,
         IoringCqFlags as IoUringCqFlags, IoringCqeFlags as IoUringCqeFlags,
         IoringEnterFlags as IoUringEnterFlags, IoringFeatureFlags as IoUringFeatureFlags,
-        IoringOp as IoUringOp, IoringRegisterOp as IoUringRegisterOp,
+        IoringFixedFdFlags as IoUringFixedFdFlags, IoringMsgringCmds as IoUringMsgRingCmd,
+        IoringMsgringFlags as IoUringMsgRingFlags, IoringOp as IoUringOp,
+        IoringRegisterFlags as IoUringRegisterFlags, IoringRegisterOp as IoUringRegisterOp,
+        IoringRestrictionOp as IoUringRestrictionOp, IoringRsrcFlags as IoUringRsrcFlags,
         IoringSetupFlags as IoUringSetupFlags, IoringSqFlags as IoUringSqFlags,
-        IoringSqeFlags as IoUringSqeFlags, IORING_OFF_CQ_RING as IOURING_OFF_CQ_RING,
-        IORING_OFF_SQES as IOURING_OFF_SQES, IORING_OFF_SQ_RING as IOURING_OFF_SQ_RING,
+        IoringSqeFlags as IoUringSqeFlags, IoringTimeoutFlags as IoUringTimeoutFlags,
+        IoringUringCmdFlags as IoUringUringCmdFlags, Timespec,
+        IORING_CQE_BUFFER_SHIFT as IOURING_CQE_BUFFER_SHIFT,
+        IORING_FILE_INDEX_ALLOC as IOURING_FILE_INDEX_ALLOC,
+        IORING_OFF_CQ_RING as IOURING_OFF_CQ_RING, IORING_OFF_SQES as IOURING_OFF_SQES,
+        IORING_OFF_SQ_RING as IOURING_OFF_SQ_RING,
     },
 };
 
@@ -34,6 +45,96 @@ pub struct IoUringWritePi {
     pub rsvd: u64,
 }
 
+// TODO: patch to rustix
+#[derive(Debug, Copy, Clone, Default)]
+#[repr(C)]
+pub struct IoUringFileIndexRange {
+    pub off: u32,
+    pub len: u32,
+    pub resv: u64,
+}
+
+// TODO: patch to rustix
+#[derive(Debug, Copy, Clone, Default)]
+#[repr(C)]
+pub struct IoUringClockRegister {
+    pub clockid: u32,
+    pub resv: [u32; 3],
+}
+
+// TODO: patch to rustix
+#[derive(Debug, Copy, Clone, Default)]
+#[repr(C)]
+pub struct IoUringBufStatus {
+    pub buf_group: u32,
+    pub head: u32,
+    pub resv: [u32; 8],
+}
+
+// TODO: patch to rustix
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum IoUringSocketOp {
+    // SOCKET_URING_OP_SIOCINQ
+    SiocInq = 0,
+
+    // SOCKET_URING_OP_SIOCOUTQ
+    SiocOutq = 1,
+
+    // SOCKET_URING_OP_GETSOCKOPT
+    GetSockOpt = 2,
+
+    // SOCKET_URING_OP_SETSOCKOPT
+    SetSockOpt = 3,
+
+    // SOCKET_URING_OP_TX_TIMESTAMP
+    TxTimestamp = 4,
+}
+
+// TODO: patch to rustix
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum IoUringNapiOp {
+    // IO_URING_NAPI_REGISTER_OP
+    Register = 0,
+
+    // IO_URING_NAPI_STATIC_ADD_ID
+    StaticAddId = 1,
+
+    // IO_URING_NAPI_STATIC_DEL_ID
+    StaticDelId = 2,
+}
+
+// TODO: patch to rustix
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum IoUringNapiTracking {
+    // IO_URING_NAPI_TRACKING_DYNAMIC
+    Dynamic = 0,
+
+    // IO_URING_NAPI_TRACKING_STATIC
+    Static = 1,
+
+    // IO_URING_NAPI_TRACKING_INACTIVE
+    Inactive = 255,
+}
+
+// TODO: patch to rustix
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TimestampFlags {}
+
+#[rustfmt::skip]
+impl TimestampFlags {
+    // IORING_TIMESTAMP_HW_SHIFT
+    pub const HW_SHIFT: u32 = 16;
+
+    // IORING_CQE_F_TSTAMP_HW
+    pub const HW: u32 = 1 << Self::HW_SHIFT;
+
+    // IORING_TIMESTAMP_TYPE_SHIFT
+    pub const TYPE_SHIFT: u32 = Self::HW_SHIFT + 1;
+}
+
 // TODO: bit flags
 #[derive(Debug, Copy, Clone, Default)]
 pub struct NopFlags {}
@@ -61,3 +162,46 @@ impl NopFlags {
     // IORING_NOP_CQE32
     pub const CQE32: u32 = 1 << 5;
 }
+
+// TODO: patch to rustix, rustix io_uring_probe holds IoringOp and can't hold unknown opcodes
+#[derive(Debug, Copy, Clone, Default)]
+#[repr(C)]
+pub struct IoUringProbeOp {
+    pub op: u8,
+    pub resv: u8,
+    pub flags: u16, // IO_URING_OP_SUPPORTED (1U << 0)
+    pub resv2: u32,
+}
+
+impl IoUringProbeOp {
+    // IO_URING_OP_SUPPORTED
+    pub const SUPPORTED: u16 = 1 << 0;
+}
+
+// TODO: patch to rustix
+#[derive(Debug, Copy, Clone)]
+#[repr(C)]
+pub struct IoUringProbe {
+    pub last_op: u8,
+    pub ops_len: u8,
+    pub resv: u16,
+    pub resv2: [u32; 3],
+    pub ops: [IoUringProbeOp; IoUringProbe::OPS_LEN],
+}
+
+impl IoUringProbe {
+    /// Max opcodes, u8 opcode space
+    pub const OPS_LEN: usize = 256;
+}
+
+impl Default for IoUringProbe {
+    fn default() -> Self {
+        Self {
+            last_op: 0,
+            ops_len: 0,
+            resv: 0,
+            resv2: [0; 3],
+            ops: [IoUringProbeOp::default(); Self::OPS_LEN],
+        }
+    }
+}
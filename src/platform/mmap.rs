@@ -3,7 +3,7 @@ use std::ptr::{null_mut, NonNull};
 use rustix::{
     fd::AsFd,
     ffi::c_void,
-    mm::{mmap, munmap, MapFlags, ProtFlags},
+    mm::{mmap, mmap_anonymous, munmap, MapFlags, ProtFlags},
 };
 
 use crate::shared::error::Result;
@@ -16,10 +16,15 @@ pub type Ptr = NonNull<c_void>;
 pub struct Mmap {
     ptr: Ptr,
     len: usize,
+    owned: bool,
 }
 
 impl Drop for Mmap {
     fn drop(&mut self) {
+        if !self.owned {
+            return;
+        }
+
         // TODO: catch error
         unsafe {
             let _ = munmap(self.ptr.as_ptr(), self.len);
@@ -39,7 +44,7 @@ impl Mmap {
             let mem = mmap(null_mut(), len, Self::MAP_PROT, Self::MAP_FLAG, fd, offset)?;
             Ptr::new_unchecked(mem)
         };
-        Ok(Self { ptr, len })
+        Ok(Self { ptr, len, owned: true })
     }
 
     pub fn mmap<Fd>(
@@ -57,7 +62,24 @@ impl Mmap {
             let mem = mmap(ptr, len, prot, flags, fd, offset)?;
             Ptr::new_unchecked(mem)
         };
-        Ok(Self { ptr, len })
+        Ok(Self { ptr, len, owned: true })
+    }
+
+    /// Anonymous shared memory, zero filled
+    pub fn anonymous(len: usize) -> Result<Self> {
+        let ptr = unsafe {
+            let mem = mmap_anonymous(null_mut(), len, Self::MAP_PROT, Self::MAP_FLAG)?;
+            Ptr::new_unchecked(mem)
+        };
+        Ok(Self { ptr, len, owned: true })
+    }
+
+    /// View of the same memory, never unmapped
+    ///
+    /// Unsafe: must not outlive self
+    #[inline]
+    pub const unsafe fn view(&self) -> Self {
+        Self { ptr: self.ptr, len: self.len, owned: false }
     }
 
     #[inline]
@@ -0,0 +1,91 @@
+use std::{
+    io::{Error, Result},
+    process::{Child, Command},
+};
+
+use bitflags::bitflags;
+pub use libc::{id_t as Id, idtype_t as IdType, pid_t as Pid, siginfo_t as SigInfo};
+
+use crate::platform::iouring::{FromRawFd, OwnedFd};
+
+// TODO: patch to rustix
+#[derive(Debug, Copy, Clone, Default)]
+pub struct IdTypes {}
+
+#[rustfmt::skip]
+impl IdTypes {
+    // P_ALL
+    pub const ALL: IdType = libc::P_ALL;
+
+    // P_PID
+    pub const PID: IdType = libc::P_PID;
+
+    // P_PIDFD
+    pub const PIDFD: IdType = libc::P_PIDFD;
+}
+
+// TODO: patch to rustix
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ChildCodes {}
+
+#[rustfmt::skip]
+impl ChildCodes {
+    // CLD_EXITED
+    pub const EXITED: i32 = libc::CLD_EXITED;
+
+    // CLD_KILLED
+    pub const KILLED: i32 = libc::CLD_KILLED;
+
+    // CLD_DUMPED
+    pub const DUMPED: i32 = libc::CLD_DUMPED;
+
+    // CLD_TRAPPED
+    pub const TRAPPED: i32 = libc::CLD_TRAPPED;
+
+    // CLD_STOPPED
+    pub const STOPPED: i32 = libc::CLD_STOPPED;
+
+    // CLD_CONTINUED
+    pub const CONTINUED: i32 = libc::CLD_CONTINUED;
+}
+
+bitflags! {
+    /// `W*` options for `waitid(2)`
+    #[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+    #[repr(transparent)]
+    pub struct WaitIdOptions: u32 {
+        const NOHANG = libc::WNOHANG as u32;
+        const STOPPED = libc::WSTOPPED as u32;
+        const EXITED = libc::WEXITED as u32;
+        const CONTINUED = libc::WCONTINUED as u32;
+        const NOWAIT = libc::WNOWAIT as u32;
+    }
+}
+
+/// `pidfd_open(2)`, available since kernel 5.3
+pub fn pidfd_open(pid: Pid) -> Result<OwnedFd> {
+    // SAFETY: pidfd_open(pid, 0) takes no pointer arguments
+    let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, pid, 0) };
+    if fd < 0 {
+        return Err(Error::last_os_error());
+    }
+
+    // SAFETY: pidfd_open returned a fresh owned fd
+    Ok(unsafe { OwnedFd::from_raw_fd(fd as _) })
+}
+
+/// Spawn `command`, return the child with its pidfd, available since kernel 5.3
+///
+/// Unreaped, the child pid can not be reused before `pidfd_open`.
+pub fn spawn_pidfd(command: &mut Command) -> Result<(Child, OwnedFd)> {
+    let mut child = command.spawn()?;
+
+    match pidfd_open(child.id() as _) {
+        Ok(pidfd) => Ok((child, pidfd)),
+        Err(error) => {
+            let _ = child.kill();
+            let _ = child.wait();
+            Err(error)
+        },
+    }
+}
@@ -128,6 +128,91 @@ impl Restriction {
     }
 }
 
+/// The NAPI tracking strategy of [`Napi`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NapiTracking {
+    /// NAPI ids are tracked from the sockets of submitted requests.
+    Dynamic,
+    /// NAPI ids are added manually with
+    /// [`register_napi_add_id`](crate::Submitter::register_napi_add_id). Available since 6.13.
+    Static,
+    /// NAPI busy polling is disabled.
+    Inactive,
+}
+
+/// NAPI busy poll settings, registered with [`register_napi`](crate::Submitter::register_napi).
+#[repr(transparent)]
+pub struct Napi(pub(crate) sys::io_uring_napi);
+
+impl Napi {
+    /// Create new NAPI settings with a zero busy poll timeout and dynamic tracking.
+    pub fn new() -> Napi {
+        let mut napi = sys::io_uring_napi::default();
+        napi.opcode = sys::IO_URING_NAPI_REGISTER_OP as _;
+        napi.op_param = sys::IO_URING_NAPI_TRACKING_DYNAMIC;
+        Napi(napi)
+    }
+
+    /// Set how long to busy poll device queues, truncated to microseconds.
+    pub fn busy_poll_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.0.busy_poll_to = timeout.as_micros().min(u32::MAX as u128) as u32;
+        self
+    }
+
+    /// Set whether to prefer busy polling over device interrupts.
+    pub fn prefer_busy_poll(mut self, prefer: bool) -> Self {
+        self.0.prefer_busy_poll = prefer as u8;
+        self
+    }
+
+    /// Set the NAPI tracking strategy.
+    pub fn tracking(mut self, tracking: NapiTracking) -> Self {
+        self.0.op_param = match tracking {
+            NapiTracking::Dynamic => sys::IO_URING_NAPI_TRACKING_DYNAMIC,
+            NapiTracking::Static => sys::IO_URING_NAPI_TRACKING_STATIC,
+            NapiTracking::Inactive => sys::IO_URING_NAPI_TRACKING_INACTIVE,
+        };
+        self
+    }
+
+    /// Get the busy poll timeout.
+    pub fn get_busy_poll_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_micros(self.0.busy_poll_to.into())
+    }
+
+    /// Get whether busy polling is preferred.
+    pub fn get_prefer_busy_poll(&self) -> bool {
+        self.0.prefer_busy_poll != 0
+    }
+
+    /// Get the NAPI tracking strategy.
+    pub fn get_tracking(&self) -> NapiTracking {
+        match self.0.op_param {
+            sys::IO_URING_NAPI_TRACKING_DYNAMIC => NapiTracking::Dynamic,
+            sys::IO_URING_NAPI_TRACKING_STATIC => NapiTracking::Static,
+            _ => NapiTracking::Inactive,
+        }
+    }
+}
+
+impl Default for Napi {
+    #[inline]
+    fn default() -> Napi {
+        Napi::new()
+    }
+}
+
+impl fmt::Debug for Napi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Napi")
+            .field("busy_poll_timeout", &self.get_busy_poll_timeout())
+            .field("prefer_busy_poll", &self.get_prefer_busy_poll())
+            .field("tracking", &self.get_tracking())
+            .finish()
+    }
+}
+
 /// A RawFd, which can be used for
 /// [register_files_update](crate::Submitter::register_files_update).
 ///
@@ -151,3 +236,80 @@ fn test_probe_layout() {
         Layout::for_value(&probe.0).align()
     );
 }
+
+/// Options of [`register_clone_buffers`](crate::Submitter::register_clone_buffers), which clones
+/// the registered buffers of another ring into this one.
+#[repr(transparent)]
+pub struct CloneBuffers(pub(crate) sys::io_uring_clone_buffers);
+
+impl CloneBuffers {
+    /// Clone all registered buffers of the ring `src_fd`, at the same indices.
+    pub fn new(src_fd: RawFd) -> CloneBuffers {
+        CloneBuffers(sys::io_uring_clone_buffers {
+            src_fd: src_fd as _,
+            ..Default::default()
+        })
+    }
+
+    /// Set whether `src_fd` is an index into the registered ring fds of the calling task rather
+    /// than a file descriptor.
+    pub fn src_registered(mut self, registered: bool) -> Self {
+        self.set_flag(sys::IORING_REGISTER_SRC_REGISTERED, registered);
+        self
+    }
+
+    /// Set the index of the first source buffer to clone.
+    ///
+    /// Available since 6.13.
+    pub fn src_offset(mut self, offset: u32) -> Self {
+        self.0.src_off = offset;
+        self
+    }
+
+    /// Set the index the first cloned buffer is installed at.
+    ///
+    /// Available since 6.13.
+    pub fn dst_offset(mut self, offset: u32) -> Self {
+        self.0.dst_off = offset;
+        self
+    }
+
+    /// Set the number of buffers to clone, 0 (the default) clones every buffer from the source
+    /// offset on.
+    ///
+    /// Available since 6.13.
+    pub fn count(mut self, nr: u32) -> Self {
+        self.0.nr = nr;
+        self
+    }
+
+    /// Set whether to replace the existing buffer table of the destination ring. Otherwise the
+    /// destination ring must not have registered buffers, or the clone fails with `EBUSY`.
+    /// Replaced buffers outside of the cloned range are kept.
+    ///
+    /// Available since 6.13.
+    pub fn replace(mut self, replace: bool) -> Self {
+        self.set_flag(sys::IORING_REGISTER_DST_REPLACE, replace);
+        self
+    }
+
+    fn set_flag(&mut self, flag: u32, enable: bool) {
+        if enable {
+            self.0.flags |= flag;
+        } else {
+            self.0.flags &= !flag;
+        }
+    }
+}
+
+impl fmt::Debug for CloneBuffers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CloneBuffers")
+            .field("src_fd", &self.0.src_fd)
+            .field("flags", &self.0.flags)
+            .field("src_off", &self.0.src_off)
+            .field("dst_off", &self.0.dst_off)
+            .field("nr", &self.0.nr)
+            .finish()
+    }
+}
@@ -2,7 +2,7 @@ use std::os::unix::io::{AsRawFd, RawFd};
 use std::sync::atomic;
 use std::{io, mem, ptr};
 
-use crate::register::{execute, Probe};
+use crate::register::{execute, CloneBuffers, Napi, Probe};
 use crate::sys;
 use crate::types::{CancelBuilder, Timespec};
 use crate::util::{cast_ptr, OwnedFd};
@@ -602,6 +602,109 @@ impl<'a> Submitter<'a> {
         .map(drop)
     }
 
+    /// Register NAPI busy poll settings, so that receive requests busy poll the device queues of
+    /// their sockets. On success `napi` is overwritten with the previous settings.
+    ///
+    /// Available since 6.9.
+    pub fn register_napi(&self, napi: &mut Napi) -> io::Result<()> {
+        napi.0.opcode = sys::IO_URING_NAPI_REGISTER_OP as _;
+        execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_REGISTER_NAPI,
+            ptr::addr_of_mut!(napi.0).cast(),
+            1,
+        )
+        .map(drop)
+    }
+
+    /// Unregister NAPI busy poll settings, returning the previous settings.
+    ///
+    /// Available since 6.9.
+    pub fn unregister_napi(&self) -> io::Result<Napi> {
+        let mut napi = Napi::new();
+        execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_UNREGISTER_NAPI,
+            ptr::addr_of_mut!(napi.0).cast(),
+            1,
+        )?;
+        Ok(napi)
+    }
+
+    /// Add a NAPI id to busy poll, e.g. the `SO_INCOMING_NAPI_ID` of a socket. Requires
+    /// [`NapiTracking::Static`](crate::register::NapiTracking::Static).
+    ///
+    /// Available since 6.13.
+    pub fn register_napi_add_id(&self, napi_id: u32) -> io::Result<()> {
+        self.register_napi_id(sys::IO_URING_NAPI_STATIC_ADD_ID, napi_id)
+    }
+
+    /// Remove a NAPI id added with [`register_napi_add_id`](Self::register_napi_add_id).
+    ///
+    /// Available since 6.13.
+    pub fn register_napi_del_id(&self, napi_id: u32) -> io::Result<()> {
+        self.register_napi_id(sys::IO_URING_NAPI_STATIC_DEL_ID, napi_id)
+    }
+
+    fn register_napi_id(&self, opcode: sys::io_uring_napi_op, napi_id: u32) -> io::Result<()> {
+        let arg = sys::io_uring_napi {
+            opcode: opcode as _,
+            op_param: napi_id,
+            ..Default::default()
+        };
+        execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_REGISTER_NAPI,
+            cast_ptr::<sys::io_uring_napi>(&arg).cast(),
+            1,
+        )
+        .map(drop)
+    }
+
+    /// Set the clock used for wait timeouts of [`submit_with_args`](Self::submit_with_args),
+    /// either `CLOCK_MONOTONIC` (the default) or `CLOCK_BOOTTIME`, which keeps counting while the
+    /// system is suspended.
+    ///
+    /// Available since 6.12.
+    pub fn register_clock(&self, clock_id: libc::clockid_t) -> io::Result<()> {
+        let arg = sys::io_uring_clock_register {
+            clockid: clock_id as _,
+            ..Default::default()
+        };
+        execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_REGISTER_CLOCK,
+            cast_ptr::<sys::io_uring_clock_register>(&arg).cast(),
+            0,
+        )
+        .map(drop)
+    }
+
+    /// Clone the registered buffers of another ring into this one, sharing the pinned pages
+    /// instead of registering the same memory again. The source ring must be owned by the same
+    /// task, and its table may be sparse, as registered by
+    /// [`register_buffers_sparse`](Self::register_buffers_sparse).
+    ///
+    /// Cloned buffers stay valid after the source ring unregisters them or exits.
+    ///
+    /// Available since 6.12.
+    ///
+    /// # Safety
+    ///
+    /// Developers must ensure that the memory of the buffers registered to the source ring is
+    /// valid and will be valid until the cloned buffers are unregistered from this ring or the
+    /// ring destroyed, even after the source ring unregistered them or exited, otherwise
+    /// undefined behaviour may occur.
+    pub unsafe fn register_clone_buffers(&self, clone: &CloneBuffers) -> io::Result<()> {
+        execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_REGISTER_CLONE_BUFFERS,
+            cast_ptr::<sys::io_uring_clone_buffers>(&clone.0).cast(),
+            1,
+        )
+        .map(drop)
+    }
+
     /// Register buffer ring for provided buffers.
     ///
     /// Details can be found in the io_uring_register_buf_ring.3 man page.
@@ -686,6 +789,49 @@ impl<'a> Submitter<'a> {
         .map(drop)
     }
 
+    /// Get the head of a registered buffer ring, the ring index of the next buffer the kernel
+    /// will pick. Buffers between this head and the tail of the ring are still available to the
+    /// kernel, the others are held by completions not yet returned to the ring.
+    ///
+    /// Only buffer rings registered with
+    /// [`register_buf_ring_with_flags`](Self::register_buf_ring_with_flags) have a head, legacy
+    /// provided buffers fail with `EINVAL`.
+    ///
+    /// Available since 6.8.
+    pub fn register_pbuf_status(&self, bgid: u16) -> io::Result<u16> {
+        let mut arg = sys::io_uring_buf_status {
+            buf_group: bgid as _,
+            ..Default::default()
+        };
+        execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_REGISTER_PBUF_STATUS,
+            ptr::addr_of_mut!(arg).cast(),
+            1,
+        )?;
+        Ok(arg.head as u16)
+    }
+
+    /// Get the status of the buffer ring registered as `bgid`: the kernel head from
+    /// [`register_pbuf_status`](Self::register_pbuf_status) and the tail published in the ring.
+    ///
+    /// Available since 6.8.
+    ///
+    /// # Safety
+    ///
+    /// `ring_base` must be the address of the first entry of the buf_ring registered as `bgid`,
+    /// see [`BufRingEntry::tail`](types::BufRingEntry::tail).
+    pub unsafe fn buf_ring_status(
+        &self,
+        bgid: u16,
+        ring_base: *const types::BufRingEntry,
+    ) -> io::Result<types::PbufStatus> {
+        let head = self.register_pbuf_status(bgid)?;
+        let tail = types::BufRingEntry::tail(ring_base).cast::<atomic::AtomicU16>();
+        let tail = (*tail).load(atomic::Ordering::Acquire);
+        Ok(types::PbufStatus { bgid, head, tail })
+    }
+
     /// Performs a synchronous cancellation request, similar to [AsyncCancel](crate::opcode::AsyncCancel),
     /// except that it completes synchronously.
     ///
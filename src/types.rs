@@ -361,6 +361,10 @@ impl BufRingEntry {
     }
 }
 
+/// Status of a registered buf_ring, see
+/// [`Submitter::buf_ring_status`](crate::Submitter::buf_ring_status).
+pub use crate::uringio::register::pbuf::PbufStatus;
+
 /// A destination slot for sending fixed resources
 /// (e.g. [`opcode::MsgRingSendFd`](crate::opcode::MsgRingSendFd)).
 #[derive(Debug, Clone, Copy)]
@@ -1,6 +1,12 @@
+pub mod buf;
+pub mod channel;
 pub mod completion;
 pub mod mmap_arena;
 pub mod operator;
+pub mod owned;
 pub mod register;
+pub mod registry;
+#[cfg(feature = "runtime")]
+pub mod runtime;
 pub mod submission;
 pub mod uring;
@@ -0,0 +1,137 @@
+use crate::{
+    shared::error::Result,
+    uringio::{register::buffers::FixedBuf, uring::enter::RingId},
+};
+
+/// IoBuf: buffer owned by an op until its completion
+///
+/// # Safety
+///
+/// `stable_ptr` must stay valid for `bytes_total` bytes and must not change when the buffer
+/// itself is moved. A buffer index must be of a buffer registered to `ring` spanning them.
+pub unsafe trait IoBuf: 'static {
+    fn stable_ptr(&self) -> *const u8;
+
+    /// Initialized bytes, the data a write op sends
+    fn bytes_init(&self) -> usize;
+
+    /// Buffer capacity, the room a read op fills
+    fn bytes_total(&self) -> usize;
+
+    /// Registered buffer index in `ring`, switches ops to their `*_FIXED` variant, fails if
+    /// registered to another ring
+    #[inline]
+    fn buf_index(&self, ring: RingId) -> Result<Option<u16>> {
+        let _ = ring;
+        Ok(None)
+    }
+}
+
+/// IoBufMut: IoBuf the kernel writes into
+///
+/// # Safety
+///
+/// See [`IoBuf`].
+pub unsafe trait IoBufMut: IoBuf {
+    fn stable_mut_ptr(&mut self) -> *mut u8;
+
+    /// # Safety
+    ///
+    /// The first `len` bytes are initialized, e.g. filled by the kernel.
+    unsafe fn set_init(&mut self, len: usize);
+}
+
+unsafe impl IoBuf for Vec<u8> {
+    #[inline]
+    fn stable_ptr(&self) -> *const u8 {
+        self.as_ptr()
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn bytes_total(&self) -> usize {
+        self.capacity()
+    }
+}
+
+unsafe impl IoBufMut for Vec<u8> {
+    #[inline]
+    fn stable_mut_ptr(&mut self) -> *mut u8 {
+        self.as_mut_ptr()
+    }
+
+    #[inline]
+    unsafe fn set_init(&mut self, len: usize) {
+        if self.len() < len {
+            self.set_len(len);
+        }
+    }
+}
+
+unsafe impl IoBuf for Box<[u8]> {
+    #[inline]
+    fn stable_ptr(&self) -> *const u8 {
+        self.as_ptr()
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn bytes_total(&self) -> usize {
+        self.len()
+    }
+}
+
+unsafe impl IoBufMut for Box<[u8]> {
+    #[inline]
+    fn stable_mut_ptr(&mut self) -> *mut u8 {
+        self.as_mut_ptr()
+    }
+
+    #[inline]
+    unsafe fn set_init(&mut self, _: usize) {}
+}
+
+unsafe impl IoBuf for FixedBuf {
+    #[inline]
+    fn stable_ptr(&self) -> *const u8 {
+        self.iovec().iov_base.cast()
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.init()
+    }
+
+    #[inline]
+    fn bytes_total(&self) -> usize {
+        self.iovec().iov_len
+    }
+
+    #[inline]
+    fn buf_index(&self, ring: RingId) -> Result<Option<u16>> {
+        self.check(ring)?;
+        Ok(Some(self.buf_index()))
+    }
+}
+
+unsafe impl IoBufMut for FixedBuf {
+    #[inline]
+    fn stable_mut_ptr(&mut self) -> *mut u8 {
+        self.iovec().iov_base.cast()
+    }
+
+    #[inline]
+    unsafe fn set_init(&mut self, len: usize) {
+        if self.init() < len {
+            self.set_len(len);
+        }
+    }
+}
@@ -0,0 +1,291 @@
+use std::{
+    fmt::{Debug, Formatter},
+    ops::Deref,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{
+    cqueue, opcode,
+    platform::iouring::{AsFd, AsRawFd, IoUringCqe, IoUringUserData, OwnedFd},
+    shared::{
+        error::{err, Result},
+        null::{Null, NULL},
+    },
+    squeue, types,
+    uringio::{operator::msg_ring::MsgRingData, submission::submitter::Submit},
+};
+
+/// Slots: bounded slab of in-flight values, indexed by MsgRing token
+#[derive(Debug)]
+struct Slots<T> {
+    values: Vec<Option<T>>,
+    free: Vec<u32>,
+}
+
+impl<T> Slots<T> {
+    fn new(capacity: u32) -> Self {
+        Self { values: (0..capacity).map(|_| None).collect(), free: (0..capacity).rev().collect() }
+    }
+
+    fn insert(&mut self, value: T) -> Result<u32, T> {
+        let Some(token) = self.free.pop() else {
+            return Err(value);
+        };
+
+        self.values[token as usize] = Some(value);
+        Ok(token)
+    }
+
+    fn remove(&mut self, token: u32) -> Option<T> {
+        let value = self.values.get_mut(token as usize)?.take()?;
+        self.free.push(token);
+        Some(value)
+    }
+}
+
+struct Shared<T> {
+    ring_fd: OwnedFd,
+    key: u64,
+    slots: Mutex<Slots<T>>,
+}
+
+/// Low bits of the source SQE user_data holding the token
+const TOKEN_MASK: u64 = u32::MAX as u64;
+
+impl<T> Shared<T> {
+    fn slots(&self) -> MutexGuard<'_, Slots<T>> {
+        // slab updates never panic halfway, a poisoned slab is still consistent
+        self.slots.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn insert(&self, value: T) -> Result<u32, T> {
+        self.slots().insert(value)
+    }
+
+    fn remove(&self, token: u32) -> Option<T> {
+        self.slots().remove(token)
+    }
+
+    /// user_data of the source SQE, failure CQEs map back to the token
+    fn source_data(&self, token: u32) -> u64 {
+        (self.key & !TOKEN_MASK) | token as u64
+    }
+}
+
+/// Create a channel to the ring `ring_fd`
+///
+/// Every message posts a CQE to the target ring with `user_data == key` and `res == token`,
+/// where token indexes the shared slab holding the value. The target ring needs no submission
+/// to receive, except `IORING_SETUP_DEFER_TASKRUN` rings, which must enter with GETEVENTS.
+///
+/// The MsgRing SQE is pushed with `IOSQE_CQE_SKIP_SUCCESS` and user_data
+/// `(key & !0xffff_ffff) | token`, keep the high 32 bits of `key` clear of other ops of the
+/// source ring. A failed message posts a CQE on the source ring, feed it to
+/// [`Sender::reclaim`] to get the value back. Tokens are CQE results, `capacity` above
+/// `i32::MAX` is rejected.
+pub fn channel<T, Fd>(ring_fd: &Fd, key: u64, capacity: u32) -> Result<(Sender<T>, Receiver<T>)>
+where
+    Fd: AsFd,
+{
+    if capacity > i32::MAX as u32 {
+        return err!("Channel capacity overflows the CQE result");
+    }
+
+    let shared = Arc::new(Shared {
+        ring_fd: ring_fd.as_fd().try_clone_to_owned()?,
+        key,
+        slots: Mutex::new(Slots::new(capacity)),
+    });
+
+    Ok((Sender { shared: shared.clone() }, Receiver { shared }))
+}
+
+/// Sender
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Send `value` through uringio Submitter, value returned if slab or queue is full
+    pub fn send<'s, Sub>(&'s self, submitter: &mut Sub, value: T) -> Result<Null, T>
+    where
+        Sub: Submit<MsgRingData<'s>>,
+    {
+        let Shared { ring_fd, key, .. } = &*self.shared;
+        let token = self.shared.insert(value)?;
+
+        let mut op = MsgRingData::new(ring_fd, IoUringUserData::from(*key), token as _).skip_cqe();
+        op.user_data = IoUringUserData::from(self.shared.source_data(token));
+        match submitter.push(op) {
+            Ok(_) => Ok(NULL),
+            Err(_) => Err(self.take(token)),
+        }
+    }
+
+    /// Send `value` through legacy [`squeue::SubmissionQueue`], see [`Sender::send`]
+    pub fn send_squeue<E>(
+        &self,
+        sq: &mut squeue::SubmissionQueue<'_, E>,
+        value: T,
+    ) -> Result<Null, T>
+    where
+        E: squeue::EntryMarker,
+    {
+        let Shared { ring_fd, key, .. } = &*self.shared;
+        let token = self.shared.insert(value)?;
+
+        let fd = types::Fd(ring_fd.as_raw_fd());
+        let entry = opcode::MsgRingData::new(fd, token as _, *key, None)
+            .build()
+            .flags(squeue::Flags::SKIP_SUCCESS)
+            .user_data(self.shared.source_data(token));
+
+        // SAFETY: target ring fd owned by channel, value owned by slab
+        match unsafe { sq.push(&entry.into()) } {
+            Ok(_) => Ok(NULL),
+            Err(_) => Err(self.take(token)),
+        }
+    }
+
+    fn take(&self, token: u32) -> T {
+        // token inserted by this sender and not yet posted
+        self.shared.remove(token).expect("channel token lost")
+    }
+
+    /// Take back the value of a failed message from its source CQE, `None` if the CQE does
+    /// not belong to this channel or the message was delivered
+    pub fn reclaim(&self, user_data: u64, res: i32) -> Option<T> {
+        if user_data & !TOKEN_MASK != self.shared.key & !TOKEN_MASK || res >= 0 {
+            return None;
+        }
+
+        self.shared.remove((user_data & TOKEN_MASK) as u32)
+    }
+
+    /// Reclaim from uringio Collector CQE
+    #[inline]
+    pub fn reclaim_cqe<C>(&self, cqe: &C) -> Option<T>
+    where
+        C: Deref<Target = IoUringCqe>,
+    {
+        self.reclaim(cqe.user_data.u64_(), cqe.res)
+    }
+
+    /// Reclaim from legacy [`cqueue::CompletionQueue`] entry
+    #[inline]
+    pub fn reclaim_entry(&self, cqe: &cqueue::Entry) -> Option<T> {
+        self.reclaim(cqe.user_data(), cqe.result())
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Self { shared: self.shared.clone() }
+    }
+}
+
+impl<T> Debug for Sender<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sender").field("key", &self.shared.key).finish()
+    }
+}
+
+/// Receiver
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Receiver<T> {
+    #[inline]
+    pub fn key(&self) -> u64 {
+        self.shared.key
+    }
+
+    /// Take the value carried by a CQE, `None` if the CQE does not belong to this channel
+    pub fn take(&self, user_data: u64, res: i32) -> Option<T> {
+        if user_data != self.shared.key || res < 0 {
+            return None;
+        }
+
+        self.shared.remove(res as u32)
+    }
+
+    /// Receive from uringio Collector CQE
+    #[inline]
+    pub fn recv<C>(&self, cqe: &C) -> Option<T>
+    where
+        C: Deref<Target = IoUringCqe>,
+    {
+        self.take(cqe.user_data.u64_(), cqe.res)
+    }
+
+    /// Receive from legacy [`cqueue::CompletionQueue`] entry
+    #[inline]
+    pub fn recv_entry(&self, cqe: &cqueue::Entry) -> Option<T> {
+        self.take(cqe.user_data(), cqe.result())
+    }
+}
+
+impl<T> Debug for Receiver<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Receiver").field("key", &self.shared.key).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slots() {
+        let mut slots = Slots::new(2);
+
+        let a = slots.insert("a").unwrap();
+        let b = slots.insert("b").unwrap();
+        assert_eq!(slots.insert("c"), Err("c"));
+
+        assert_eq!(slots.remove(a), Some("a"));
+        assert_eq!(slots.remove(a), None);
+        assert_eq!(slots.insert("d"), Ok(a));
+        assert_eq!(slots.remove(b), Some("b"));
+        assert_eq!(slots.remove(2), None);
+    }
+
+    #[test]
+    fn test_reclaim() {
+        let fd = std::fs::File::open("/dev/null").unwrap();
+        let (tx, rx) = channel::<&str>(&fd, 0x1234_5678_0000_0001, 2).unwrap();
+        let token = tx.shared.insert("a").unwrap();
+        let user_data = tx.shared.source_data(token);
+        assert_eq!(user_data, 0x1234_5678_0000_0000 | token as u64);
+
+        // delivered messages and other channels are left alone
+        assert_eq!(tx.reclaim(user_data, 0), None);
+        assert_eq!(tx.reclaim(user_data ^ (1 << 32), -libc::EBADFD), None);
+        assert_eq!(tx.reclaim(user_data, -libc::EBADFD), Some("a"));
+        assert_eq!(rx.take(rx.key(), token as _), None);
+    }
+
+    #[test]
+    fn test_capacity() {
+        let fd = std::fs::File::open("/dev/null").unwrap();
+        assert!(channel::<()>(&fd, 1, i32::MAX as u32 + 1).is_err());
+    }
+
+    #[test]
+    fn test_poisoned_slots() {
+        let fd = std::fs::File::open("/dev/null").unwrap();
+        let (tx, rx) = channel::<&str>(&fd, 1, 2).unwrap();
+
+        let shared = tx.shared.clone();
+        let _ = std::thread::spawn(move || {
+            let _slots = shared.slots.lock();
+            panic!("poison slots");
+        })
+        .join();
+        assert!(tx.shared.slots.is_poisoned());
+
+        let token = tx.shared.insert("a").unwrap();
+        assert_eq!(rx.take(rx.key(), token as _), Some("a"));
+    }
+}
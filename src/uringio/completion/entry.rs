@@ -1,10 +1,17 @@
 use std::{
+    io::Error,
     marker::PhantomData,
     mem::transmute,
     ops::{Deref, DerefMut},
 };
 
-use crate::platform::iouring::{IoUringCqe, IoUringCqeFlags, IoUringSetupFlags};
+use crate::{
+    cqueue,
+    platform::iouring::{IoUringCqe, IoUringCqeFlags, IoUringSetupFlags, IOURING_CQE_BUFFER_SHIFT},
+    shared::error::Result,
+    sys,
+    uringio::operator::{Op, OpOutput, PlainOutput},
+};
 
 #[derive(Debug)]
 pub enum Ty {
@@ -59,6 +66,81 @@ impl DerefMut for Cqe16 {
     }
 }
 
+macro_rules! impl_cqe {
+    ($($cqe:ty),*) => {$(
+        impl $cqe {
+            /// Result of the CQE, negative res mapped to errno
+            #[inline]
+            pub fn result(&self) -> Result<u32> {
+                match self.res {
+                    res if res < 0 => Err(Error::from_raw_os_error(-res)),
+                    res => Ok(res as _),
+                }
+            }
+
+            /// Decode the output of op `T`, see [`plain_output`](Self::plain_output) for outputs
+            /// owning nothing
+            ///
+            /// # Safety
+            ///
+            /// The CQE must complete an op of type `T`, see [`OpOutput::from_cqe`].
+            #[inline]
+            pub unsafe fn output<T: Op>(&self) -> Result<T::Output> {
+                let res = self.result()?;
+                Ok(T::Output::from_cqe(res, self.flags))
+            }
+
+            /// Decode the output of op `T` owning nothing, e.g. a byte count
+            ///
+            /// A CQE of another op decodes to a meaningless but harmless value.
+            #[inline]
+            pub fn plain_output<T>(&self) -> Result<T::Output>
+            where
+                T: Op,
+                T::Output: PlainOutput,
+            {
+                let res = self.result()?;
+                // SAFETY: PlainOutput decodes from any res and flags
+                Ok(unsafe { T::Output::from_cqe(res, self.flags) })
+            }
+
+            /// Selected buffer id, `IORING_CQE_F_BUFFER`
+            #[inline]
+            pub fn buffer_select(&self) -> Option<u16> {
+                self.flags
+                    .contains(IoUringCqeFlags::BUFFER)
+                    .then(|| (self.flags.bits() >> IOURING_CQE_BUFFER_SHIFT) as u16)
+            }
+
+            /// More CQEs will be posted for the same SQE, `IORING_CQE_F_MORE`
+            #[inline]
+            pub fn more(&self) -> bool {
+                self.flags.contains(IoUringCqeFlags::MORE)
+            }
+
+            /// Socket has more data to read, `IORING_CQE_F_SOCK_NONEMPTY`
+            #[inline]
+            pub fn sock_nonempty(&self) -> bool {
+                self.flags.contains(IoUringCqeFlags::SOCK_NONEMPTY)
+            }
+
+            /// Zero copy notification, `IORING_CQE_F_NOTIF`
+            #[inline]
+            pub fn notif(&self) -> bool {
+                self.flags.contains(IoUringCqeFlags::NOTIF)
+            }
+        }
+    )*};
+}
+
+impl_cqe!(Cqe16, Cqe32, CqeMix);
+
+impl From<&Cqe16> for cqueue::Entry {
+    fn from(cqe: &Cqe16) -> Self {
+        cqueue::Entry(legacy_cqe(cqe))
+    }
+}
+
 /// Cqe32
 #[derive(Debug, Default)]
 #[repr(C)]
@@ -93,6 +175,21 @@ impl DerefMut for Cqe32 {
     }
 }
 
+impl From<&Cqe32> for cqueue::Entry32 {
+    fn from(cqe: &Cqe32) -> Self {
+        cqueue::Entry32(cqueue::Entry(legacy_cqe(cqe)), cqe.ext_data)
+    }
+}
+
+fn legacy_cqe(cqe: &IoUringCqe) -> sys::io_uring_cqe {
+    sys::io_uring_cqe {
+        user_data: cqe.user_data.u64_(),
+        res: cqe.res,
+        flags: cqe.flags.bits(),
+        big_cqe: Default::default(),
+    }
+}
+
 /// CqeMixed
 #[derive(Debug, Default)]
 #[repr(C)]
@@ -164,6 +261,54 @@ mod tests {
         assert_eq!(size_of::<CqeMix>(), 16);
     }
 
+    #[test]
+    fn test_result() {
+        use crate::uringio::operator::fs::Read;
+
+        let mut cqe = Cqe16::default();
+        cqe.res = 42;
+        assert_eq!(cqe.result().unwrap(), 42);
+        assert_eq!(unsafe { cqe.output::<Read>() }.unwrap(), 42);
+        assert_eq!(cqe.plain_output::<Read>().unwrap(), 42);
+
+        cqe.res = -libc::EAGAIN;
+        let err = cqe.result().unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EAGAIN));
+        let err = cqe.plain_output::<Read>().unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EAGAIN));
+    }
+
+    #[test]
+    fn test_flags() {
+        let mut cqe = CqeMix::default();
+        assert_eq!(cqe.buffer_select(), None);
+
+        cqe.flags = IoUringCqeFlags::from_bits_retain(7 << IOURING_CQE_BUFFER_SHIFT)
+            | IoUringCqeFlags::BUFFER
+            | IoUringCqeFlags::MORE;
+        assert_eq!(cqe.buffer_select(), Some(7));
+        assert!(cqe.more());
+        assert!(!cqe.sock_nonempty());
+        assert!(!cqe.notif());
+    }
+
+    #[test]
+    fn test_into_entry() {
+        let mut cqe = Cqe32 { ext_data: [1, 2], ..Default::default() };
+        cqe.user_data = 0x42u64.into();
+        cqe.res = -1;
+        cqe.flags = IoUringCqeFlags::MORE;
+
+        let entry = cqueue::Entry32::from(&cqe);
+        assert_eq!(entry.user_data(), 0x42);
+        assert_eq!(entry.result(), -1);
+        assert!(cqueue::more(entry.flags()));
+        assert_eq!(entry.big_cqe(), &[1, 2]);
+
+        let entry = cqueue::Entry::from(&Cqe16::default());
+        assert_eq!(entry.user_data(), 0);
+    }
+
     #[test]
     fn test_cqe_mix_transmute() {
         assert_eq!(size_of::<Cqe16>(), size_of::<CqeMix>());
@@ -66,6 +66,21 @@ impl<'fd, S, C, M> CompletionQueue<'fd, S, C, M> {
         IoUringSqFlags::from_bits_retain(bits)
     }
 
+    #[inline]
+    pub fn eventfd_disabled(&self) -> bool {
+        self.flags(Ordering::Acquire).contains(IoUringCqFlags::EVENTFD_DISABLED)
+    }
+
+    /// Toggle `IORING_CQ_EVENTFD_DISABLED`, suppress registered eventfd notifications
+    pub fn set_eventfd_disabled(&self, disabled: bool) {
+        let bit = IoUringCqFlags::EVENTFD_DISABLED.bits();
+        if disabled {
+            self.k_flags.fetch_or(bit, Ordering::Release);
+        } else {
+            self.k_flags.fetch_and(!bit, Ordering::Release);
+        }
+    }
+
     pub fn overflow(&self) -> u32 {
         self.k_overflow.load(Ordering::Acquire)
     }
@@ -9,7 +9,10 @@ use crate::{
         mmap::Mmap,
     },
     shared::error::Result,
-    uringio::{completion::entry::Cqe, submission::entry::Sqe, uring::args::UringArgs},
+    uringio::{
+        completion::entry::Cqe, register::ring_fds::RegisteredFd, submission::entry::Sqe,
+        uring::args::UringArgs,
+    },
 };
 
 /// MmapArena
@@ -48,6 +51,14 @@ where
         }
     }
 
+    /// Views of the `IORING_SETUP_NO_MMAP` memory owned by `fd`, SQ and CQ rings share one
+    /// region
+    pub fn new_registered(fd: &'fd RegisteredFd) -> Self {
+        // SAFETY: fd outlives 'fd
+        let (sq_mmap, sqes_mmap) = unsafe { (fd.rings.view(), fd.sqes.view()) };
+        Self { sq_mmap, sqes_mmap, cq_mmap: None, _marker_: PhantomData }
+    }
+
     #[inline]
     pub const fn cq_mmap(&self) -> &Mmap {
         match &self.cq_mmap {
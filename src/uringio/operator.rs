@@ -1,11 +1,19 @@
+pub mod epoll;
 pub mod fd;
 pub mod fs;
+pub mod msg_ring;
 pub mod net;
 pub mod nop;
 pub mod opcode;
+pub mod process;
+pub mod time;
+pub mod uring_cmd;
+
+pub use io_uring_macros::op;
 
 use crate::{
-    platform::iouring::IoUringOp,
+    platform::iouring::{FromRawFd, IoUringCqeFlags, IoUringOp, OwnedFd},
+    shared::null::{Null, NULL},
     uringio::submission::entry::{FixSqe, Sqe, Sqe128, Sqe64},
 };
 
@@ -19,10 +27,12 @@ mod private {
     impl Sealed for Sqe128 {}
 }
 
-// TODO: use attr macro to impl Op
+/// Op: SQE layout of an operation, see [`macro@op`] to generate one
 pub trait Op: Sized {
     type Entry: private::Sealed;
 
+    type Output: OpOutput;
+
     const OP_CODE: IoUringOp;
 
     // FIX: Associated constants lazy evaluation, do check in test
@@ -35,6 +45,55 @@ pub trait Op: Sized {
 
 impl<T> FixSqe for T where T: Op {}
 
+/// OpOutput: result of an op decoded from its succeeded CQE
+pub trait OpOutput: Sized {
+    /// # Safety
+    ///
+    /// `res` and `flags` must come from a CQE of the op, decoded at most once, e.g. an fd
+    /// result is owned by the output.
+    unsafe fn from_cqe(res: u32, flags: IoUringCqeFlags) -> Self;
+}
+
+/// PlainOutput: output owning nothing, decoded from any CQE res and flags
+///
+/// # Safety
+///
+/// [`OpOutput::from_cqe`] must be sound for any `res` and `flags`.
+pub unsafe trait PlainOutput: OpOutput {}
+
+impl OpOutput for Null {
+    #[inline]
+    unsafe fn from_cqe(_: u32, _: IoUringCqeFlags) -> Self {
+        NULL
+    }
+}
+
+impl OpOutput for u32 {
+    #[inline]
+    unsafe fn from_cqe(res: u32, _: IoUringCqeFlags) -> Self {
+        res
+    }
+}
+
+impl OpOutput for usize {
+    #[inline]
+    unsafe fn from_cqe(res: u32, _: IoUringCqeFlags) -> Self {
+        res as _
+    }
+}
+
+// SAFETY: decoded from res only, no resource owned
+unsafe impl PlainOutput for Null {}
+unsafe impl PlainOutput for u32 {}
+unsafe impl PlainOutput for usize {}
+
+impl OpOutput for OwnedFd {
+    #[inline]
+    unsafe fn from_cqe(res: u32, _: IoUringCqeFlags) -> Self {
+        OwnedFd::from_raw_fd(res as _)
+    }
+}
+
 #[cfg(feature = "unstable-toolchain")]
 mod _unsafe_transmute_ {
     use std::intrinsics::transmute_unchecked;
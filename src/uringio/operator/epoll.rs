@@ -0,0 +1,5 @@
+mod epoll_ctl;
+mod epoll_wait;
+
+pub use epoll_ctl::EpollCtl;
+pub use epoll_wait::EpollWait;
@@ -0,0 +1,83 @@
+use std::ptr;
+
+use crate::{
+    platform::{
+        epoll::{EpollCtlOp, EpollEvent},
+        iouring::{AsFd, AsRawFd, IoUringPtr, IoUringSqeFlags, RawFd},
+    },
+    shared::null::Null,
+    uringio::operator::{fd::OpFd, op},
+};
+
+#[op(code = EpollCtl, entry = Sqe64, output = Null, init = raw)]
+pub struct EpollCtl<'fd, 'ev> {
+    #[sqe(ioprio, default = 0)]
+    pub ioprio: u16,
+    #[sqe(fd)]
+    pub epfd: RawFd,
+    #[sqe(off)]
+    pub target_fd: u64,
+    #[sqe(addr)]
+    pub event: IoUringPtr,
+    #[sqe(len)]
+    pub op: EpollCtlOp,
+    #[sqe(personality, default = 0, set = personality)]
+    pub personality: u16,
+}
+
+impl<'fd, 'ev> EpollCtl<'fd, 'ev> {
+    fn new<Ep, Fd>(epfd: &'fd Ep, fd: &'fd Fd, op: EpollCtlOp, event: *const EpollEvent) -> Self
+    where
+        Ep: OpFd,
+        Fd: AsFd,
+    {
+        let target_fd = fd.as_fd().as_raw_fd() as _;
+        let event = IoUringPtr::new(event.cast_mut().cast());
+        let mut op = Self::raw(epfd.raw_fd(), target_fd, event, op);
+        op.flags = Ep::SQE_FLAG;
+        op
+    }
+
+    /// `EPOLL_CTL_ADD`
+    pub fn add<Ep, Fd>(epfd: &'fd Ep, fd: &'fd Fd, event: &'ev EpollEvent) -> Self
+    where
+        Ep: OpFd,
+        Fd: AsFd,
+    {
+        Self::new(epfd, fd, EpollCtlOp::Add, event)
+    }
+
+    /// `EPOLL_CTL_MOD`
+    pub fn modify<Ep, Fd>(epfd: &'fd Ep, fd: &'fd Fd, event: &'ev EpollEvent) -> Self
+    where
+        Ep: OpFd,
+        Fd: AsFd,
+    {
+        Self::new(epfd, fd, EpollCtlOp::Mod, event)
+    }
+
+    /// `EPOLL_CTL_DEL`
+    pub fn delete<Ep, Fd>(epfd: &'fd Ep, fd: &'fd Fd) -> Self
+    where
+        Ep: OpFd,
+        Fd: AsFd,
+    {
+        Self::new(epfd, fd, EpollCtlOp::Del, ptr::null())
+    }
+
+    pub fn skip_cqe(mut self) -> Self {
+        self.flags |= IoUringSqeFlags::CQE_SKIP_SUCCESS;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uringio::operator::Op;
+
+    #[test]
+    fn test_size_align() {
+        EpollCtl::check_size_align();
+    }
+}
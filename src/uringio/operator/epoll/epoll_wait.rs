@@ -0,0 +1,52 @@
+use crate::{
+    platform::{
+        epoll::EpollEvent,
+        iouring::{IoUringPtr, IoUringSqeFlags, RawFd},
+    },
+    uringio::operator::{fd::OpFd, op},
+};
+
+/// EpollWait: available since kernel 6.15
+#[op(code = EpollWait, entry = Sqe64, output = usize, init = raw)]
+pub struct EpollWait<'fd, 'dst> {
+    #[sqe(ioprio, default = 0)]
+    pub ioprio: u16,
+    #[sqe(fd)]
+    pub epfd: RawFd,
+    #[sqe(addr)]
+    pub events: IoUringPtr,
+    #[sqe(len)]
+    pub max_events: u32,
+    #[sqe(op_flags, default = 0)]
+    pub wait_flags: u32,
+    #[sqe(personality, default = 0, set = personality)]
+    pub personality: u16,
+}
+
+impl<'fd, 'dst> EpollWait<'fd, 'dst> {
+    pub fn new<Ep>(epfd: &'fd Ep, events: &'dst mut [EpollEvent]) -> Self
+    where
+        Ep: OpFd,
+    {
+        let ptr = IoUringPtr::new(events.as_mut_ptr().cast());
+        let mut op = Self::raw(epfd.raw_fd(), ptr, events.len() as _);
+        op.flags = Ep::SQE_FLAG;
+        op
+    }
+
+    pub fn skip_cqe(mut self) -> Self {
+        self.flags |= IoUringSqeFlags::CQE_SKIP_SUCCESS;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uringio::operator::Op;
+
+    #[test]
+    fn test_size_align() {
+        EpollWait::check_size_align();
+    }
+}
@@ -1,3 +1,9 @@
+mod fixed_fd_install;
+
+use std::marker::PhantomData;
+
+pub use fixed_fd_install::FixedFdInstall;
+
 use crate::platform::iouring::{AsFd, AsRawFd, IoUringSqeFlags, NopFlags, RawFd};
 
 pub trait OpFd {
@@ -8,23 +14,34 @@ pub trait OpFd {
     fn raw_fd(&self) -> RawFd;
 }
 
-/// FixFd
-#[derive(Debug, Copy, Clone)]
+/// FixFd: slot of the registered file table, bounded by table lifetime `'t`
+///
+/// Not `Copy`, the handle is unique per slot and given back to free the slot.
+#[derive(Debug)]
 #[repr(transparent)]
-pub struct FixFd {
+pub struct FixFd<'t> {
     idx: RawFd,
+    _marker_: PhantomData<&'t ()>,
 }
 
-impl From<usize> for FixFd {
-    /// Restrict: idx <= Fd limit <= i32::MAX
+impl<'t> FixFd<'t> {
+    /// Restrict: idx < table size <= i32::MAX
+    ///
+    /// # Safety
+    ///
+    /// `idx` must be a slot of the file table registered to the ring, for lifetime `'t`.
+    #[inline]
+    pub const unsafe fn new_unchecked(idx: u32) -> Self {
+        Self { idx: idx as _, _marker_: PhantomData }
+    }
+
     #[inline]
-    fn from(idx: usize) -> Self {
-        // FIXME: overflow
-        Self { idx: idx as _ }
+    pub const fn index(&self) -> u32 {
+        self.idx as _
     }
 }
 
-impl OpFd for FixFd {
+impl<'t> OpFd for FixFd<'t> {
     const NOP_FLAG: u32 = NopFlags::FILE | NopFlags::FIXED_FILE;
     const SQE_FLAG: IoUringSqeFlags = IoUringSqeFlags::FIXED_FILE;
 
@@ -0,0 +1,49 @@
+use crate::{
+    platform::iouring::{IoUringFixedFdFlags, IoUringSqeFlags, OwnedFd, RawFd},
+    uringio::operator::{
+        fd::{FixFd, OpFd},
+        op,
+    },
+};
+
+/// FixedFdInstall: install a fixed file slot into the process fd table, available since kernel 6.8
+///
+/// The new fd is returned in CQE res, `O_CLOEXEC` unless [`FixedFdInstall::no_cloexec`].
+#[op(code = FixedFdInstall, entry = Sqe64, output = OwnedFd, init = raw)]
+pub struct FixedFdInstall<'t> {
+    #[sqe(fd)]
+    pub fd: RawFd,
+    #[sqe(op_flags, default = IoUringFixedFdFlags::empty())]
+    pub install_flags: IoUringFixedFdFlags,
+    #[sqe(personality, default = 0, set = personality)]
+    pub personality: u16,
+}
+
+impl<'t> FixedFdInstall<'t> {
+    pub fn new(fd: &'t FixFd<'_>) -> Self {
+        let mut op = Self::raw(fd.raw_fd());
+        op.flags = FixFd::SQE_FLAG;
+        op
+    }
+
+    pub fn no_cloexec(mut self) -> Self {
+        self.install_flags |= IoUringFixedFdFlags::NO_CLOEXEC;
+        self
+    }
+
+    pub fn skip_cqe(mut self) -> Self {
+        self.flags |= IoUringSqeFlags::CQE_SKIP_SUCCESS;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uringio::operator::Op;
+
+    #[test]
+    fn test_size_align() {
+        FixedFdInstall::check_size_align();
+    }
+}
@@ -1,40 +1,29 @@
-use std::marker::PhantomData;
-
 use crate::{
     platform::{
         io::ReadWriteFlags,
-        iouring::{IoUringOp, IoUringPiAttr, IoUringPtr, IoUringSqeFlags, IoUringUserData, RawFd},
-    },
-    uringio::{
-        operator::{fd::OpFd, Op},
-        submission::entry::Sqe64,
+        iouring::{IoUringPiAttr, IoUringPtr, RawFd},
     },
+    uringio::operator::{fd::OpFd, op},
 };
 
-#[derive(Debug)]
-#[repr(C)]
+#[op(code = Read, entry = Sqe64, output = usize, init = raw)]
 pub struct Read<'fd, 'dst> {
-    pub opcode: IoUringOp,
-    pub flags: IoUringSqeFlags,
+    #[sqe(ioprio, default = 0)]
     pub ioprio: u16,
+    #[sqe(fd)]
     pub fd: RawFd,
+    #[sqe(off, default = 0)]
     pub offset: u64,
+    #[sqe(addr)]
     pub ptr: IoUringPtr,
+    #[sqe(len)]
     pub len: u32,
+    #[sqe(op_flags, default = Default::default())]
     pub rw_flags: ReadWriteFlags,
-    pub user_data: IoUringUserData,
-    _unused0_: [u8; 2],
+    #[sqe(personality, default = 0, set = personality)]
     pub personality: u16,
-    _unused1_: [u8; 4],
+    #[sqe(offset = 48, size = 16, default = Default::default())]
     pub pi_attr: IoUringPiAttr,
-
-    _marker_: PhantomData<(&'fd (), &'dst mut [u8])>,
-}
-
-impl<'fd, 'dst> Op for Read<'fd, 'dst> {
-    type Entry = Sqe64;
-
-    const OP_CODE: IoUringOp = IoUringOp::Read;
 }
 
 impl<'fd, 'dst> Read<'fd, 'dst> {
@@ -42,28 +31,17 @@ impl<'fd, 'dst> Read<'fd, 'dst> {
     where
         Fd: OpFd,
     {
-        Self {
-            opcode: Self::OP_CODE,
-            flags: Fd::SQE_FLAG,
-            ioprio: 0,
-            fd: fd.raw_fd(),
-            offset: 0,
-            ptr: IoUringPtr::new(dst.as_mut_ptr().cast()),
-            len: dst.len() as _,
-            rw_flags: Default::default(),
-            user_data: Default::default(),
-            _unused0_: Default::default(),
-            personality: Default::default(),
-            _unused1_: Default::default(),
-            pi_attr: Default::default(),
-            _marker_: PhantomData,
-        }
+        let ptr = IoUringPtr::new(dst.as_mut_ptr().cast());
+        let mut op = Self::raw(fd.raw_fd(), ptr, dst.len() as _);
+        op.flags = Fd::SQE_FLAG;
+        op
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::uringio::operator::Op;
 
     #[test]
     fn test_size_align() {
@@ -0,0 +1,5 @@
+mod msg_ring_data;
+mod msg_ring_send_fd;
+
+pub use msg_ring_data::MsgRingData;
+pub use msg_ring_send_fd::MsgRingSendFd;
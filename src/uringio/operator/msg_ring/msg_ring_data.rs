@@ -0,0 +1,61 @@
+use crate::{
+    platform::iouring::{
+        IoUringCqeFlags, IoUringMsgRingCmd, IoUringMsgRingFlags, IoUringSqeFlags, IoUringUserData,
+        RawFd,
+    },
+    shared::null::Null,
+    uringio::operator::{fd::OpFd, op},
+};
+
+/// MsgRingData: post a CQE carrying `target_data`, `res` and `cqe_flags` to the target ring
+#[op(code = MsgRing, entry = Sqe64, output = Null, init = raw)]
+pub struct MsgRingData<'fd> {
+    #[sqe(fd)]
+    pub ring_fd: RawFd,
+    #[sqe(off)]
+    pub target_data: IoUringUserData,
+    #[sqe(addr, default = IoUringMsgRingCmd::Data as u64)]
+    pub cmd: u64,
+    #[sqe(len)]
+    pub res: i32,
+    #[sqe(op_flags, default = IoUringMsgRingFlags::empty())]
+    pub msg_ring_flags: IoUringMsgRingFlags,
+    #[sqe(personality, default = 0)]
+    pub personality: u16,
+    #[sqe(file_index, default = IoUringCqeFlags::empty())]
+    pub cqe_flags: IoUringCqeFlags,
+}
+
+impl<'fd> MsgRingData<'fd> {
+    pub fn new<Fd>(ring_fd: &'fd Fd, target_data: IoUringUserData, res: i32) -> Self
+    where
+        Fd: OpFd,
+    {
+        let mut op = Self::raw(ring_fd.raw_fd(), target_data, res);
+        op.flags = Fd::SQE_FLAG;
+        op
+    }
+
+    /// Pass `cqe_flags` to the target CQE, require `IORING_MSG_RING_FLAGS_PASS` (kernel 6.3)
+    pub fn set_cqe_flags(mut self, cqe_flags: IoUringCqeFlags) -> Self {
+        self.cqe_flags = cqe_flags;
+        self.msg_ring_flags |= IoUringMsgRingFlags::FLAGS_PASS;
+        self
+    }
+
+    pub fn skip_cqe(mut self) -> Self {
+        self.flags |= IoUringSqeFlags::CQE_SKIP_SUCCESS;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uringio::operator::Op;
+
+    #[test]
+    fn test_size_align() {
+        MsgRingData::check_size_align();
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{
+    platform::iouring::{
+        IoUringMsgRingCmd, IoUringMsgRingFlags, IoUringSqeFlags, IoUringUserData, RawFd,
+        IOURING_FILE_INDEX_ALLOC,
+    },
+    shared::null::Null,
+    uringio::operator::{
+        fd::{FixFd, OpFd},
+        op,
+    },
+};
+
+/// MsgRingSendFd: install a fixed file of this ring into the fixed file table of the target ring
+#[op(code = MsgRing, entry = Sqe64, output = Null, init = raw)]
+pub struct MsgRingSendFd<'fd> {
+    #[sqe(fd)]
+    pub ring_fd: RawFd,
+    #[sqe(off)]
+    pub target_data: IoUringUserData,
+    #[sqe(addr, default = IoUringMsgRingCmd::SendFd as u64)]
+    pub cmd: u64,
+    #[sqe(op_flags, default = IoUringMsgRingFlags::empty())]
+    pub msg_ring_flags: IoUringMsgRingFlags,
+    #[sqe(personality, default = 0)]
+    pub personality: u16,
+    #[sqe(file_index, default = IOURING_FILE_INDEX_ALLOC as _)]
+    pub dst_slot: u32,
+    #[sqe(addr3)]
+    pub src_fd: u64,
+}
+
+impl<'fd> MsgRingSendFd<'fd> {
+    /// Target slot defaults to `IORING_FILE_INDEX_ALLOC`
+    pub fn new<Fd>(ring_fd: &'fd Fd, src_fd: &FixFd<'_>, target_data: IoUringUserData) -> Self
+    where
+        Fd: OpFd,
+    {
+        let mut op = Self::raw(ring_fd.raw_fd(), target_data, src_fd.raw_fd() as _);
+        op.flags = Fd::SQE_FLAG;
+        op
+    }
+
+    /// Install into slot `dst_slot` of the target fixed file table, replacing its file
+    pub fn set_dst_slot(mut self, dst_slot: &FixFd<'_>) -> Self {
+        // file_index encoded as slot + 1
+        self.dst_slot = dst_slot.raw_fd() as u32 + 1;
+        self
+    }
+
+    /// Do not post a CQE to the target ring (`IORING_MSG_RING_CQE_SKIP`)
+    pub fn skip_target_cqe(mut self) -> Self {
+        self.msg_ring_flags |= IoUringMsgRingFlags::CQE_SKIP;
+        self
+    }
+
+    pub fn skip_cqe(mut self) -> Self {
+        self.flags |= IoUringSqeFlags::CQE_SKIP_SUCCESS;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uringio::operator::Op;
+
+    #[test]
+    fn test_size_align() {
+        MsgRingSendFd::check_size_align();
+    }
+}
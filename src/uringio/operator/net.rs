@@ -1 +1,3 @@
+mod socket_cmd;
 
+pub use socket_cmd::{SocketCmd, TxTimestamp};
@@ -0,0 +1,142 @@
+use std::{ptr::null_mut, time::Duration};
+
+use crate::{
+    platform::iouring::{
+        IoUringCqeFlags, IoUringPtr, IoUringSocketOp, IoUringSqeFlags, RawFd, TimestampFlags,
+    },
+    uringio::{
+        completion::entry::Cqe32,
+        operator::{fd::OpFd, op},
+    },
+};
+
+/// SocketCmd: socket `IORING_OP_URING_CMD`, available since kernel 6.7
+#[op(code = UringCmd, entry = Sqe64, output = u32, init = raw)]
+pub struct SocketCmd<'fd, 'val> {
+    #[sqe(fd)]
+    pub fd: RawFd,
+    #[sqe(offset = 8, size = 4)]
+    pub cmd_op: IoUringSocketOp,
+    #[sqe(offset = 16, size = 4, default = 0)]
+    pub level: i32,
+    #[sqe(offset = 20, size = 4, default = 0)]
+    pub optname: i32,
+    #[sqe(personality, default = 0, set = personality)]
+    pub personality: u16,
+    #[sqe(file_index, default = 0)]
+    pub optlen: u32,
+    #[sqe(addr3, default = IoUringPtr::new(null_mut()))]
+    pub optval: IoUringPtr,
+}
+
+impl<'fd, 'val> SocketCmd<'fd, 'val> {
+    fn new<Fd>(fd: &'fd Fd, cmd_op: IoUringSocketOp) -> Self
+    where
+        Fd: OpFd,
+    {
+        let mut op = Self::raw(fd.raw_fd(), cmd_op);
+        op.flags = Fd::SQE_FLAG;
+        op
+    }
+
+    /// Bytes in the receive queue, returned in CQE res
+    pub fn siocinq<Fd>(fd: &'fd Fd) -> Self
+    where
+        Fd: OpFd,
+    {
+        Self::new(fd, IoUringSocketOp::SiocInq)
+    }
+
+    /// Bytes in the send queue, returned in CQE res
+    pub fn siocoutq<Fd>(fd: &'fd Fd) -> Self
+    where
+        Fd: OpFd,
+    {
+        Self::new(fd, IoUringSocketOp::SiocOutq)
+    }
+
+    /// Read option into `optval`, the option length returned in CQE res
+    pub fn getsockopt<Fd, T>(fd: &'fd Fd, level: i32, optname: i32, optval: &'val mut T) -> Self
+    where
+        Fd: OpFd,
+        T: Copy,
+    {
+        Self {
+            level,
+            optname,
+            optlen: size_of::<T>() as _,
+            optval: IoUringPtr::new((optval as *mut T).cast()),
+            ..Self::new(fd, IoUringSocketOp::GetSockOpt)
+        }
+    }
+
+    pub fn setsockopt<Fd, T>(fd: &'fd Fd, level: i32, optname: i32, optval: &'val T) -> Self
+    where
+        Fd: OpFd,
+        T: Copy,
+    {
+        Self {
+            level,
+            optname,
+            optlen: size_of::<T>() as _,
+            optval: IoUringPtr::new((optval as *const T).cast_mut().cast()),
+            ..Self::new(fd, IoUringSocketOp::SetSockOpt)
+        }
+    }
+
+    /// Drain TX timestamps from the socket error queue, available since kernel 6.17
+    ///
+    /// Require `IORING_SETUP_CQE32`, each timestamp is posted as a CQE with
+    /// `IORING_CQE_F_MORE` and decoded by [`TxTimestamp::from_cqe`].
+    pub fn tx_timestamp<Fd>(fd: &'fd Fd) -> Self
+    where
+        Fd: OpFd,
+    {
+        Self::new(fd, IoUringSocketOp::TxTimestamp)
+    }
+
+    pub fn skip_cqe(mut self) -> Self {
+        self.flags |= IoUringSqeFlags::CQE_SKIP_SUCCESS;
+        self
+    }
+}
+
+/// TxTimestamp: TX timestamp carried by a Cqe32
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TxTimestamp {
+    /// `SOF_TIMESTAMPING_OPT_ID` key
+    pub key: u32,
+    /// `SCM_TSTAMP_*` type
+    pub ty: u32,
+    pub hw: bool,
+    pub time: Duration,
+}
+
+impl TxTimestamp {
+    /// Decode the timestamp, `None` for error and final CQEs
+    pub fn from_cqe(cqe: &Cqe32) -> Option<Self> {
+        if cqe.res < 0 || !cqe.flags.contains(IoUringCqeFlags::MORE) {
+            return None;
+        }
+
+        let flags = cqe.flags.bits();
+        let [sec, nsec] = *cqe.ext_data();
+        Some(Self {
+            key: cqe.res as _,
+            ty: flags >> TimestampFlags::TYPE_SHIFT,
+            hw: flags & TimestampFlags::HW != 0,
+            time: Duration::new(sec, nsec as _),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uringio::operator::Op;
+
+    #[test]
+    fn test_size_align() {
+        SocketCmd::check_size_align();
+    }
+}
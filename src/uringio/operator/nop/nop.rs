@@ -1,54 +1,22 @@
 use crate::{
-    platform::iouring::{IoUringOp, IoUringSqeFlags, IoUringUserData, NopFlags, RawFd},
-    uringio::{
-        operator::{fd::OpFd, Op},
-        submission::entry::Sqe64,
-    },
+    platform::iouring::{IoUringSqeFlags, NopFlags, RawFd},
+    shared::null::Null,
+    uringio::operator::{fd::OpFd, op},
 };
 
-#[derive(Debug)]
-#[repr(C)]
+#[op(code = Nop, entry = Sqe64, output = Null, init = new)]
 pub struct Nop {
-    pub opcode: IoUringOp,
-    pub flags: IoUringSqeFlags,
-    _unused0_: [u8; 2],
+    #[sqe(fd, default = -1)]
     pub fd: RawFd,
-    _unused1_: u64,
-    _unused2_: u64,
+    #[sqe(len, default = 0)]
     pub len: u32,
+    #[sqe(op_flags, default = NopFlags::NONE)]
     pub nop_flags: u32, // TODO: NopFlags
-    pub user_data: IoUringUserData,
+    #[sqe(buf_index, default = 0)]
     pub buf_index: u16,
-    _unused3_: [u8; 2],
-    _unused4_: [u8; 4],
-    _unused5_: [u8; 16],
-}
-
-impl Op for Nop {
-    type Entry = Sqe64;
-
-    const OP_CODE: IoUringOp = IoUringOp::Nop;
 }
 
 impl Nop {
-    pub fn new() -> Self {
-        Self {
-            opcode: Self::OP_CODE,
-            flags: IoUringSqeFlags::default(),
-            _unused0_: Default::default(),
-            fd: -1,
-            _unused1_: 0,
-            _unused2_: 0,
-            len: 0,
-            nop_flags: NopFlags::NONE,
-            user_data: Default::default(),
-            buf_index: Default::default(),
-            _unused3_: Default::default(),
-            _unused4_: Default::default(),
-            _unused5_: Default::default(),
-        }
-    }
-
     pub fn set_fd<Fd>(mut self, fd: Fd) -> Self
     where
         Fd: OpFd,
@@ -78,6 +46,7 @@ impl Nop {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::uringio::operator::Op;
 
     #[test]
     fn test_size_align() {
@@ -1,54 +1,26 @@
 use crate::{
-    platform::iouring::{IoUringOp, IoUringSqeFlags, IoUringUserData, NopFlags, RawFd},
-    uringio::{
-        operator::{fd::OpFd, Op},
-        submission::entry::Sqe64,
-    },
+    platform::iouring::{IoUringSqeFlags, NopFlags, RawFd},
+    shared::null::Null,
+    uringio::operator::{fd::OpFd, op},
 };
 
-#[derive(Debug)]
-#[repr(C)]
+#[op(code = Nop128, entry = Sqe64, output = Null, init = new)]
 pub struct Nop128 {
-    pub opcode: IoUringOp,
-    pub flags: IoUringSqeFlags,
-    _unused0_: [u8; 2],
+    #[sqe(fd, default = -1)]
     pub fd: RawFd,
+    #[sqe(off, default = 0)]
     pub ext_data1: u64,
+    #[sqe(addr, default = 0)]
     pub ext_data2: u64,
+    #[sqe(len, default = 0)]
     pub len: u32,
+    #[sqe(op_flags, default = NopFlags::NONE)]
     pub nop_flags: u32, // TODO: NopFlags
-    pub user_data: IoUringUserData,
+    #[sqe(buf_index, default = 0)]
     pub buf_index: u16,
-    _unused1_: [u8; 2],
-    _unused2_: [u8; 4],
-    _unused3_: [u8; 16],
-}
-
-impl Op for Nop128 {
-    type Entry = Sqe64;
-
-    const OP_CODE: IoUringOp = IoUringOp::Nop128;
 }
 
 impl Nop128 {
-    pub fn new() -> Self {
-        Self {
-            opcode: Self::OP_CODE,
-            flags: IoUringSqeFlags::default(),
-            _unused0_: Default::default(),
-            fd: -1,
-            ext_data1: 0,
-            ext_data2: 0,
-            len: 0,
-            nop_flags: NopFlags::NONE,
-            user_data: Default::default(),
-            buf_index: Default::default(),
-            _unused1_: Default::default(),
-            _unused2_: Default::default(),
-            _unused3_: Default::default(),
-        }
-    }
-
     pub fn set_fd<Fd>(mut self, fd: Fd) -> Self
     where
         Fd: OpFd,
@@ -85,6 +57,7 @@ impl Nop128 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::uringio::operator::Op;
 
     #[test]
     fn test_size_align() {
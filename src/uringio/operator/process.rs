@@ -0,0 +1,3 @@
+mod waitid;
+
+pub use waitid::{WaitId, WaitIdTarget, WaitInfo, WaitStatus};
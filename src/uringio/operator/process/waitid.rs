@@ -0,0 +1,168 @@
+use std::{
+    fmt::{Debug, Formatter},
+    mem,
+};
+
+use crate::{
+    platform::{
+        iouring::{AsRawFd, BorrowedFd, IoUringPtr, IoUringSqeFlags},
+        process::{ChildCodes, IdType, IdTypes, Pid, SigInfo, WaitIdOptions},
+    },
+    shared::null::Null,
+    uringio::operator::op,
+};
+
+/// WaitIdTarget: `P_ALL`, `P_PID` or `P_PIDFD`
+#[derive(Debug, Copy, Clone)]
+pub enum WaitIdTarget<'fd> {
+    All,
+    Pid(Pid),
+    PidFd(BorrowedFd<'fd>),
+}
+
+impl<'fd> WaitIdTarget<'fd> {
+    #[inline]
+    pub fn id_type(&self) -> IdType {
+        match self {
+            Self::All => IdTypes::ALL,
+            Self::Pid(_) => IdTypes::PID,
+            Self::PidFd(_) => IdTypes::PIDFD,
+        }
+    }
+
+    #[inline]
+    pub fn id(&self) -> i32 {
+        match self {
+            Self::All => 0,
+            Self::Pid(pid) => *pid,
+            Self::PidFd(fd) => fd.as_raw_fd(),
+        }
+    }
+}
+
+/// WaitStatus: decoded `si_code` & `si_status` of child state change
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WaitStatus {
+    /// Child called `_exit`, with exit status
+    Exited(i32),
+    /// Child killed by signal
+    Killed(i32),
+    /// Child killed by signal and dumped core
+    Dumped(i32),
+    /// Traced child has trapped
+    Trapped(i32),
+    /// Child stopped by signal
+    Stopped(i32),
+    /// Stopped child continued by `SIGCONT`
+    Continued(i32),
+}
+
+/// WaitInfo: owned `siginfo_t` filled by kernel on completion
+#[repr(transparent)]
+pub struct WaitInfo {
+    raw: SigInfo,
+}
+
+impl WaitInfo {
+    pub fn new() -> Self {
+        // SAFETY: siginfo_t is plain old data, all zero is valid
+        Self { raw: unsafe { mem::zeroed() } }
+    }
+
+    #[inline]
+    pub const fn as_raw(&self) -> &SigInfo {
+        &self.raw
+    }
+
+    /// Child pid, 0 if no child changed state (`WNOHANG`)
+    #[inline]
+    pub fn pid(&self) -> Pid {
+        // SAFETY: SIGCHLD siginfo layout written by waitid
+        unsafe { self.raw.si_pid() }
+    }
+
+    pub fn status(&self) -> Option<WaitStatus> {
+        if self.pid() == 0 {
+            return None;
+        }
+
+        // SAFETY: SIGCHLD siginfo layout written by waitid
+        let status = unsafe { self.raw.si_status() };
+
+        match self.raw.si_code {
+            ChildCodes::EXITED => Some(WaitStatus::Exited(status)),
+            ChildCodes::KILLED => Some(WaitStatus::Killed(status)),
+            ChildCodes::DUMPED => Some(WaitStatus::Dumped(status)),
+            ChildCodes::TRAPPED => Some(WaitStatus::Trapped(status)),
+            ChildCodes::STOPPED => Some(WaitStatus::Stopped(status)),
+            ChildCodes::CONTINUED => Some(WaitStatus::Continued(status)),
+            _ => None,
+        }
+    }
+}
+
+impl Default for WaitInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for WaitInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WaitInfo")
+            .field("pid", &self.pid())
+            .field("status", &self.status())
+            .finish()
+    }
+}
+
+#[op(code = Waitid, entry = Sqe64, output = Null, init = raw)]
+pub struct WaitId<'fd, 'info> {
+    #[sqe(fd)]
+    pub id: i32,
+    #[sqe(addr2)]
+    pub infop: IoUringPtr,
+    #[sqe(len)]
+    pub id_type: IdType,
+    #[sqe(op_flags, default = 0)]
+    pub waitid_flags: u32,
+    #[sqe(personality, default = 0, set = personality)]
+    pub personality: u16,
+    #[sqe(file_index, default = WaitIdOptions::EXITED, set)]
+    pub options: WaitIdOptions,
+}
+
+impl<'fd, 'info> WaitId<'fd, 'info> {
+    /// Wait for child exit, options default to `WEXITED`
+    ///
+    /// `info` is written at completion, see [`WaitIdOwned`] to hand it over to the op instead.
+    ///
+    /// [`WaitIdOwned`]: crate::uringio::owned::WaitIdOwned
+    pub fn new(target: WaitIdTarget<'fd>, info: &'info mut WaitInfo) -> Self {
+        let infop = IoUringPtr::new((&raw mut info.raw).cast());
+        Self::raw(target.id(), infop, target.id_type())
+    }
+
+    pub fn skip_cqe(mut self) -> Self {
+        self.flags |= IoUringSqeFlags::CQE_SKIP_SUCCESS;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uringio::operator::Op;
+
+    #[test]
+    fn test_size_align() {
+        WaitId::check_size_align();
+    }
+
+    #[test]
+    fn test_wait_info_empty() {
+        let info = WaitInfo::new();
+        assert_eq!(info.pid(), 0);
+        assert_eq!(info.status(), None);
+    }
+}
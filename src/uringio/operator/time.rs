@@ -0,0 +1,5 @@
+mod deadline;
+mod timeout;
+
+pub use deadline::{Clock, Deadline};
+pub use timeout::Timeout;
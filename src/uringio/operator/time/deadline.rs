@@ -0,0 +1,147 @@
+use std::{
+    io::Error,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{
+    platform::iouring::{ClockId, IoUringTimeoutFlags, Timespec},
+    shared::error::{err, Result},
+};
+
+/// Clock: clock a timeout runs on
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Clock {
+    /// `CLOCK_MONOTONIC`, stops while suspended, the clock of [`Instant`]
+    #[default]
+    Monotonic,
+    /// `CLOCK_BOOTTIME`, keeps running while suspended
+    Boottime,
+    /// `CLOCK_REALTIME`, the clock of [`SystemTime`]
+    Realtime,
+}
+
+impl Clock {
+    #[inline]
+    pub const fn clock_id(self) -> ClockId {
+        match self {
+            Self::Monotonic => ClockId::Monotonic,
+            Self::Boottime => ClockId::Boottime,
+            Self::Realtime => ClockId::Realtime,
+        }
+    }
+
+    /// `IORING_TIMEOUT_BOOTTIME` or `IORING_TIMEOUT_REALTIME`
+    #[inline]
+    pub const fn timeout_flags(self) -> IoUringTimeoutFlags {
+        match self {
+            Self::Monotonic => IoUringTimeoutFlags::empty(),
+            Self::Boottime => IoUringTimeoutFlags::BOOTTIME,
+            Self::Realtime => IoUringTimeoutFlags::REALTIME,
+        }
+    }
+
+    /// Current time of the clock
+    pub fn now(self) -> Result<Duration> {
+        let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+        // SAFETY: ts valid during syscall
+        if unsafe { libc::clock_gettime(self.clock_id() as _, &mut ts) } < 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(Duration::new(ts.tv_sec as _, ts.tv_nsec as _))
+    }
+}
+
+/// Deadline: expiration of a timeout, relative or absolute on a [`Clock`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Deadline {
+    ts: Timespec,
+    clock: Clock,
+    abs: bool,
+}
+
+impl Deadline {
+    /// Expire `timeout` after submission
+    pub fn after(clock: Clock, timeout: Duration) -> Result<Self> {
+        Ok(Self { ts: Self::timespec_of(timeout)?, clock, abs: false })
+    }
+
+    /// Expire when `clock` reaches `time` (`IORING_TIMEOUT_ABS`)
+    pub fn at(clock: Clock, time: Duration) -> Result<Self> {
+        Ok(Self { ts: Self::timespec_of(time)?, clock, abs: true })
+    }
+
+    /// Expire at `instant` on [`Clock::Monotonic`]
+    pub fn from_instant(instant: Instant) -> Result<Self> {
+        let (now, clock_now) = (Instant::now(), Clock::Monotonic.now()?);
+        let time = match instant.checked_duration_since(now) {
+            Some(remain) => clock_now.saturating_add(remain),
+            None => clock_now.saturating_sub(now - instant),
+        };
+        Self::at(Clock::Monotonic, time)
+    }
+
+    /// Expire at `time` on [`Clock::Realtime`], a time before the epoch expires at once
+    pub fn from_system_time(time: SystemTime) -> Result<Self> {
+        let time = time.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+        Self::at(Clock::Realtime, time)
+    }
+
+    /// Expire `timeout` from now on [`Clock::Boottime`], counting time spent suspended
+    pub fn boottime_after(timeout: Duration) -> Result<Self> {
+        let time = Clock::Boottime.now()?.saturating_add(timeout);
+        Self::at(Clock::Boottime, time)
+    }
+
+    #[inline]
+    pub const fn timespec(&self) -> &Timespec {
+        &self.ts
+    }
+
+    #[inline]
+    pub const fn clock(&self) -> Clock {
+        self.clock
+    }
+
+    #[inline]
+    pub const fn is_absolute(&self) -> bool {
+        self.abs
+    }
+
+    /// Timeout flags of the clock, with `IORING_TIMEOUT_ABS` if absolute
+    pub fn flags(&self) -> IoUringTimeoutFlags {
+        let mut flags = self.clock.timeout_flags();
+        if self.abs {
+            flags |= IoUringTimeoutFlags::ABS;
+        }
+        flags
+    }
+
+    fn timespec_of(time: Duration) -> Result<Timespec> {
+        match Timespec::try_from(time) {
+            Ok(ts) => Ok(ts),
+            Err(_) => err!("Timeout overflow"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadline() {
+        let deadline = Deadline::after(Clock::Boottime, Duration::from_millis(1500)).unwrap();
+        assert_eq!(deadline.timespec().tv_sec, 1);
+        assert_eq!(deadline.timespec().tv_nsec, 500_000_000);
+        assert_eq!(deadline.flags(), IoUringTimeoutFlags::BOOTTIME);
+
+        let deadline = Deadline::from_system_time(SystemTime::UNIX_EPOCH).unwrap();
+        assert_eq!(deadline.flags(), IoUringTimeoutFlags::REALTIME | IoUringTimeoutFlags::ABS);
+        assert_eq!(deadline.timespec().tv_sec, 0);
+
+        let now = Clock::Monotonic.now().unwrap();
+        let deadline = Deadline::from_instant(Instant::now() + Duration::from_secs(5)).unwrap();
+        assert!(deadline.timespec().tv_sec >= (now.as_secs() + 4) as i64);
+        assert!(deadline.is_absolute());
+    }
+}
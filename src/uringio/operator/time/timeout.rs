@@ -0,0 +1,48 @@
+use crate::{
+    platform::iouring::{IoUringPtr, IoUringSqeFlags, IoUringTimeoutFlags, RawFd},
+    shared::null::Null,
+    uringio::operator::{op, time::Deadline},
+};
+
+/// Timeout: `IORING_OP_TIMEOUT`, complete at `deadline` with `ETIME`, or after `count` CQEs
+#[op(code = Timeout, entry = Sqe64, output = Null, init = raw)]
+pub struct Timeout<'ts> {
+    #[sqe(ioprio, default = 0)]
+    pub ioprio: u16,
+    #[sqe(fd, default = -1)]
+    pub fd: RawFd,
+    /// Complete early once `count` other CQEs are posted
+    #[sqe(off, default = 0, set)]
+    pub count: u64,
+    #[sqe(addr)]
+    pub ts: IoUringPtr,
+    #[sqe(len, default = 1)]
+    pub len: u32,
+    #[sqe(op_flags)]
+    pub timeout_flags: IoUringTimeoutFlags,
+    #[sqe(personality, default = 0, set = personality)]
+    pub personality: u16,
+}
+
+impl<'ts> Timeout<'ts> {
+    pub fn new(deadline: &'ts Deadline) -> Self {
+        let ts = IoUringPtr::new((&raw const *deadline.timespec()).cast_mut().cast());
+        Self::raw(ts, deadline.flags())
+    }
+
+    pub fn skip_cqe(mut self) -> Self {
+        self.flags |= IoUringSqeFlags::CQE_SKIP_SUCCESS;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uringio::operator::Op;
+
+    #[test]
+    fn test_size_align() {
+        Timeout::check_size_align();
+    }
+}
@@ -0,0 +1,172 @@
+mod uring_cmd16;
+mod uring_cmd80;
+
+pub use uring_cmd16::UringCmd16;
+pub use uring_cmd80::UringCmd80;
+
+use crate::{
+    platform::iouring::IoUringCqeFlags,
+    shared::error::Result,
+    uringio::{
+        completion::entry::{Cqe16, Cqe32},
+        operator::{fd::OpFd, Op},
+        submission::entry::{Sqe128, Sqe64},
+    },
+};
+
+mod private {
+    use super::*;
+
+    /// Sealed CmdEntry: Sqe64 and Sqe128
+    pub trait Sealed {}
+
+    impl Sealed for Sqe64 {}
+    impl Sealed for Sqe128 {}
+}
+
+/// CmdEntry: SQE size of a passthrough command
+pub trait CmdEntry: private::Sealed {
+    /// Payload bytes at `cmd` of the SQE, 16 bytes for Sqe64 and 80 bytes for Sqe128
+    type Payload;
+
+    type Op<'fd>: Op<Entry = Self>;
+
+    fn new_op<'fd, Fd>(fd: &'fd Fd, cmd_op: u32) -> Self::Op<'fd>
+    where
+        Fd: OpFd;
+
+    fn payload<'a>(op: &'a mut Self::Op<'_>) -> &'a mut Self::Payload;
+}
+
+impl CmdEntry for Sqe64 {
+    type Payload = [u8; 16];
+
+    type Op<'fd> = UringCmd16<'fd>;
+
+    #[inline]
+    fn new_op<'fd, Fd>(fd: &'fd Fd, cmd_op: u32) -> Self::Op<'fd>
+    where
+        Fd: OpFd,
+    {
+        UringCmd16::new(fd, cmd_op)
+    }
+
+    #[inline]
+    fn payload<'a>(op: &'a mut Self::Op<'_>) -> &'a mut Self::Payload {
+        &mut op.cmd
+    }
+}
+
+impl CmdEntry for Sqe128 {
+    type Payload = [u8; 80];
+
+    type Op<'fd> = UringCmd80<'fd>;
+
+    #[inline]
+    fn new_op<'fd, Fd>(fd: &'fd Fd, cmd_op: u32) -> Self::Op<'fd>
+    where
+        Fd: OpFd,
+    {
+        UringCmd80::new(fd, cmd_op)
+    }
+
+    #[inline]
+    fn payload<'a>(op: &'a mut Self::Op<'_>) -> &'a mut Self::Payload {
+        &mut op.cmd
+    }
+}
+
+/// Op built from the passthrough command `C`
+pub type CmdOp<'fd, C> = <<C as UringCmd>::Entry as CmdEntry>::Op<'fd>;
+
+/// UringCmd: driver defined passthrough command, e.g. ublk, NVMe, fuse
+///
+/// Pointers written into the payload must stay valid until the command completes.
+pub trait UringCmd: Sized {
+    /// Sqe64 for commands fit in 16 bytes, Sqe128 (`IORING_SETUP_SQE128`) otherwise
+    type Entry: CmdEntry;
+
+    type Output;
+
+    const CMD_OP: u32;
+
+    /// Lay out the command in zeroed `uring_cmd()` bytes
+    fn encode(&self, payload: &mut <Self::Entry as CmdEntry>::Payload);
+
+    /// Decode a succeeded CQE, `ext_data` is zeroed for Cqe16
+    fn decode(res: u32, flags: IoUringCqeFlags, ext_data: &[u64; 2]) -> Result<Self::Output>;
+
+    /// Build the op targeting `fd`
+    fn build<'fd, Fd>(&self, fd: &'fd Fd) -> CmdOp<'fd, Self>
+    where
+        Fd: OpFd,
+    {
+        let mut op = Self::Entry::new_op(fd, Self::CMD_OP);
+        self.encode(Self::Entry::payload(&mut op));
+        op
+    }
+
+    /// Decode a Cqe16, negative res mapped to errno
+    fn decode_cqe16(cqe: &Cqe16) -> Result<Self::Output> {
+        Self::decode(cqe.result()?, cqe.flags, &[0; 2])
+    }
+
+    /// Decode a Cqe32, negative res mapped to errno
+    fn decode_cqe32(cqe: &Cqe32) -> Result<Self::Output> {
+        Self::decode(cqe.result()?, cqe.flags, cqe.ext_data())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::iouring::IoUringOp;
+
+    /// NopCmd: test command carrying two words, decoded as res and ext data
+    struct NopCmd {
+        data: [u64; 2],
+    }
+
+    impl UringCmd for NopCmd {
+        type Entry = Sqe128;
+        type Output = (u32, [u64; 2]);
+
+        const CMD_OP: u32 = 0x42;
+
+        fn encode(&self, payload: &mut [u8; 80]) {
+            payload[..8].copy_from_slice(&self.data[0].to_ne_bytes());
+            payload[8..16].copy_from_slice(&self.data[1].to_ne_bytes());
+        }
+
+        fn decode(res: u32, _: IoUringCqeFlags, ext_data: &[u64; 2]) -> Result<Self::Output> {
+            Ok((res, *ext_data))
+        }
+    }
+
+    #[test]
+    fn test_build() {
+        let cmd = NopCmd { data: [1, 2] };
+        let stdin = std::io::stdin();
+        let op = cmd.build(&stdin);
+        assert_eq!(op.opcode, IoUringOp::UringCmd);
+        assert_eq!(op.cmd_op, NopCmd::CMD_OP);
+
+        let mut sqe = Sqe128::from(op);
+        let payload = sqe.uring_cmd();
+        assert_eq!(payload[..8], 1u64.to_ne_bytes());
+        assert_eq!(payload[8..16], 2u64.to_ne_bytes());
+        assert_eq!(payload[16..], [0; 64]);
+    }
+
+    #[test]
+    fn test_decode() {
+        let mut cqe = Cqe32::default();
+        cqe.res = 7;
+        assert_eq!(NopCmd::decode_cqe32(&cqe).unwrap(), (7, [0; 2]));
+
+        let mut cqe = Cqe16::default();
+        cqe.res = -libc::EOPNOTSUPP;
+        let err = NopCmd::decode_cqe16(&cqe).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EOPNOTSUPP));
+    }
+}
@@ -0,0 +1,82 @@
+use std::{ptr, ptr::null_mut};
+
+use crate::{
+    platform::iouring::{IoUringPtr, IoUringSqeFlags, IoUringUringCmdFlags, RawFd},
+    uringio::operator::{fd::OpFd, op},
+};
+
+/// UringCmd16: driver passthrough command with a 16 bytes payload
+#[op(code = UringCmd, entry = Sqe64, output = u32, init = raw)]
+pub struct UringCmd16<'fd> {
+    #[sqe(ioprio, default = 0)]
+    pub ioprio: u16,
+    #[sqe(fd)]
+    pub fd: RawFd,
+    #[sqe(offset = 8, size = 4)]
+    pub cmd_op: u32,
+    #[sqe(addr, default = IoUringPtr::new(null_mut()))]
+    pub addr: IoUringPtr,
+    #[sqe(len, default = 0)]
+    pub len: u32,
+    #[sqe(op_flags, default = IoUringUringCmdFlags::empty())]
+    pub uring_cmd_flags: IoUringUringCmdFlags,
+    #[sqe(buf_index, default = 0)]
+    pub buf_index: u16,
+    #[sqe(personality, default = 0, set = personality)]
+    pub personality: u16,
+    #[sqe(cmd, default = [0; 16])]
+    pub cmd: [u8; 16],
+}
+
+impl<'fd> UringCmd16<'fd> {
+    pub fn new<Fd>(fd: &'fd Fd, cmd_op: u32) -> Self
+    where
+        Fd: OpFd,
+    {
+        let mut op = Self::raw(fd.raw_fd(), cmd_op);
+        op.flags = Fd::SQE_FLAG;
+        op
+    }
+
+    /// Copy `payload` to the head of cmd bytes, the rest stays zeroed
+    pub fn set_payload<P>(mut self, payload: P) -> Self
+    where
+        P: Copy,
+    {
+        const { assert!(size_of::<P>() <= 16, "UringCmd16 payload overflow") };
+        // SAFETY: payload size checked, cmd bytes unaligned
+        unsafe { ptr::write_unaligned(self.cmd.as_mut_ptr().cast(), payload) };
+        self
+    }
+
+    /// Use registered buffer `buf_index` (`IORING_URING_CMD_FIXED`)
+    pub fn set_buf_index(mut self, buf_index: u16) -> Self {
+        self.buf_index = buf_index;
+        self.uring_cmd_flags |= IoUringUringCmdFlags::FIXED;
+        self
+    }
+
+    pub fn skip_cqe(mut self) -> Self {
+        self.flags |= IoUringSqeFlags::CQE_SKIP_SUCCESS;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uringio::operator::Op;
+
+    #[test]
+    fn test_size_align() {
+        UringCmd16::check_size_align();
+    }
+
+    #[test]
+    fn test_payload() {
+        let stdin = std::io::stdin();
+        let op = UringCmd16::new(&stdin, 0).set_payload([1u32, 2u32]);
+        assert_eq!(op.cmd[..8], [1, 0, 0, 0, 2, 0, 0, 0]);
+        assert_eq!(op.cmd[8..], [0; 8]);
+    }
+}
@@ -0,0 +1,287 @@
+mod read;
+mod waitid;
+mod write;
+
+use std::{
+    any::Any,
+    fmt::{Debug, Formatter},
+    io::Error,
+    marker::PhantomData,
+    mem,
+    ops::Deref,
+};
+
+pub use read::ReadOwned;
+pub use waitid::WaitIdOwned;
+pub use write::WriteOwned;
+
+use crate::{
+    platform::iouring::{IoUringCqe, IoUringCqeFlags, IoUringUserData},
+    shared::error::Result,
+    uringio::{
+        registry::Registry,
+        submission::{entry::Sqe64, submitter::RingSubmit},
+        uring::enter::RingId,
+    },
+};
+
+/// OwnedOp: op owning its buffers and fds until its completion
+///
+/// # Safety
+///
+/// The SQE built may only reference memory owned by self which stays put when self moves, and
+/// resources registered to `ring`.
+pub unsafe trait OwnedOp: 'static {
+    type Output: 'static;
+
+    /// Build the SQE for `ring`, fails if a registered resource belongs to another ring
+    fn build(&mut self, ring: RingId) -> Result<Sqe64>;
+
+    /// Complete with the result of the final CQE, handing the resources back
+    fn complete(self, res: Result<u32>) -> Self::Output;
+}
+
+/// BufResult: op result with its resources handed back
+#[derive(Debug)]
+pub struct BufResult<T, Fd, B> {
+    pub result: Result<T>,
+    pub fd: Fd,
+    pub buf: B,
+}
+
+pub(crate) trait InFlight {
+    fn complete(self: Box<Self>, res: Result<u32>) -> Box<dyn Any>;
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<O> InFlight for O
+where
+    O: OwnedOp,
+{
+    fn complete(self: Box<Self>, res: Result<u32>) -> Box<dyn Any> {
+        Box::new(OwnedOp::complete(*self, res))
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+enum State {
+    InFlight(Box<dyn InFlight>),
+    Done(Box<dyn Any>),
+    Taken,
+}
+
+/// Ticket: claim on the output of an op pushed to a [`Driver`]
+#[derive(Debug)]
+pub struct Ticket<O> {
+    user_data: IoUringUserData,
+    _marker_: PhantomData<fn() -> O>,
+}
+
+impl<O> Ticket<O> {
+    #[inline]
+    pub fn user_data(&self) -> IoUringUserData {
+        self.user_data
+    }
+}
+
+/// Driver: safe submission of [`OwnedOp`]s
+///
+/// Ops are kept until their final CQE, so buffers and fds outlive the kernel access. A driver
+/// dropped with ops in flight leaks them instead of freeing memory the kernel may still use.
+pub struct Driver {
+    registry: Registry<State>,
+    in_flight: usize,
+}
+
+impl Default for Driver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Driver {
+    pub const fn new() -> Self {
+        Self { registry: Registry::new(), in_flight: 0 }
+    }
+
+    /// Ops pushed and not completed yet
+    #[inline]
+    pub const fn in_flight(&self) -> usize {
+        self.in_flight
+    }
+
+    /// Push `op`, handed back if the submission queue is full
+    ///
+    /// An op failing to build is completed with the error right away.
+    pub fn push<O, Sub>(&mut self, submitter: &mut Sub, mut op: O) -> Result<Ticket<O>, O>
+    where
+        O: OwnedOp,
+        Sub: RingSubmit,
+    {
+        let mut sqe = match op.build(submitter.ring()) {
+            Ok(sqe) => sqe,
+            Err(err) => {
+                let done: Box<dyn Any> = Box::new(op.complete(Err(err)));
+                let user_data = self.registry.insert(State::Done(done));
+                return Ok(Ticket { user_data, _marker_: PhantomData });
+            },
+        };
+        let user_data = self.registry.insert(State::InFlight(Box::new(op)));
+        sqe.user_data = user_data;
+
+        if submitter.push(sqe).is_err() {
+            let Some(State::InFlight(op)) = self.registry.remove(user_data) else {
+                unreachable!("op registered above")
+            };
+            return Err(*op.into_any().downcast::<O>().expect("op type registered above"));
+        }
+
+        self.in_flight += 1;
+        Ok(Ticket { user_data, _marker_: PhantomData })
+    }
+
+    /// Complete the op of `cqe`, false if the CQE does not belong to this driver
+    pub fn complete<C>(&mut self, cqe: &C) -> bool
+    where
+        C: Deref<Target = IoUringCqe>,
+    {
+        let Some(state) = self.registry.get_mut(cqe.user_data) else {
+            return false;
+        };
+
+        // owned ops are single shot, wait for the final CQE
+        if !matches!(state, State::InFlight(_)) || cqe.flags.contains(IoUringCqeFlags::MORE) {
+            return matches!(state, State::InFlight(_));
+        }
+
+        let State::InFlight(op) = mem::replace(state, State::Taken) else {
+            unreachable!("state checked above")
+        };
+
+        let res = match cqe.res {
+            res if res < 0 => Err(Error::from_raw_os_error(-res)),
+            res => Ok(res as u32),
+        };
+
+        *state = State::Done(op.complete(res));
+        self.in_flight -= 1;
+        true
+    }
+
+    /// Complete ops of CQEs, e.g. a Collector, return the number of completed CQEs
+    pub fn dispatch<'c, I, C>(&mut self, cqes: I) -> usize
+    where
+        I: IntoIterator<Item = &'c C>,
+        C: Deref<Target = IoUringCqe> + 'c,
+    {
+        cqes.into_iter().filter(|cqe| self.complete(*cqe)).count()
+    }
+
+    /// Take the output of `ticket`, handed back if the op is still in flight
+    pub fn take<O>(&mut self, ticket: Ticket<O>) -> Result<O::Output, Ticket<O>>
+    where
+        O: OwnedOp,
+    {
+        if !matches!(self.registry.get(ticket.user_data), Some(State::Done(_))) {
+            return Err(ticket);
+        }
+
+        let Some(State::Done(output)) = self.registry.remove(ticket.user_data) else {
+            unreachable!("state checked above")
+        };
+        Ok(*output.downcast::<O::Output>().expect("ticket type mismatch"))
+    }
+}
+
+impl Debug for Driver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Driver")
+            .field("len", &self.registry.len())
+            .field("in_flight", &self.in_flight)
+            .finish()
+    }
+}
+
+impl Drop for Driver {
+    fn drop(&mut self) {
+        if self.in_flight > 0 {
+            // kernel may still access in flight buffers
+            mem::forget(mem::take(&mut self.registry));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        platform::{iouring::OwnedFd, process::WaitIdOptions},
+        shared::null::{Null, NULL},
+        uringio::{
+            completion::entry::Cqe16,
+            submission::submitter::{private, Submit},
+        },
+    };
+
+    struct Queue(Vec<Sqe64>, RingId);
+
+    impl Submit<Sqe64> for Queue {
+        fn push(&mut self, sqe: Sqe64) -> Result<Null, Sqe64> {
+            self.0.push(sqe);
+            Ok(NULL)
+        }
+    }
+
+    impl private::Sealed for Queue {}
+
+    impl RingSubmit for Queue {
+        fn ring(&self) -> RingId {
+            self.1
+        }
+    }
+
+    #[test]
+    fn test_driver() {
+        let file = OwnedFd::from(std::fs::File::open("/dev/null").unwrap());
+        let read = ReadOwned::new(file, Vec::with_capacity(8));
+
+        let mut queue = Queue(Vec::new(), RingId::next());
+        let mut driver = Driver::new();
+        let ticket = driver.push(&mut queue, read).unwrap();
+        assert_eq!(driver.in_flight(), 1);
+
+        let ticket = driver.take(ticket).unwrap_err();
+
+        let mut cqe = Cqe16::default();
+        cqe.user_data = queue.0[0].user_data;
+        cqe.res = 3;
+        assert_eq!(driver.dispatch([&cqe]), 1);
+        assert_eq!(driver.in_flight(), 0);
+
+        let done = driver.take(ticket).unwrap();
+        assert_eq!(done.result.unwrap(), 3);
+        assert_eq!(done.buf.len(), 3);
+        assert!(driver.registry.is_empty());
+    }
+
+    #[test]
+    fn test_waitid_owned() {
+        let options = WaitIdOptions::EXITED | WaitIdOptions::NOHANG;
+        let mut waitid = WaitIdOwned::pid(42).set_options(options);
+        let sqe = waitid.build(RingId::next()).unwrap();
+        assert_eq!(sqe.fd, 42);
+        assert_eq!(
+            unsafe { sqe.splice_fd_in_or_file_index_or_addr_len.file_index },
+            options.bits()
+        );
+
+        let done = waitid.complete(Ok(0));
+        assert!(done.result.is_ok());
+        assert!(done.fd.is_none());
+        assert_eq!(done.buf.status(), None);
+    }
+}
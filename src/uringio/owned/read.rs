@@ -0,0 +1,79 @@
+use crate::{
+    platform::iouring::{AsFd, AsRawFd, IoUringOp, IoUringPtr, IoUringSqe},
+    shared::error::Result,
+    uringio::{
+        buf::IoBufMut,
+        owned::{BufResult, OwnedOp},
+        register::personality::Personality,
+        submission::entry::Sqe64,
+        uring::enter::RingId,
+    },
+};
+
+/// ReadOwned: `IORING_OP_READ`, or `IORING_OP_READ_FIXED` for registered buffers
+///
+/// Fill `buf` from its head up to `bytes_total`, the read length becomes initialized.
+#[derive(Debug)]
+pub struct ReadOwned<Fd, B> {
+    fd: Fd,
+    buf: B,
+    offset: u64,
+    personality: u16,
+}
+
+impl<Fd, B> ReadOwned<Fd, B>
+where
+    Fd: AsFd + 'static,
+    B: IoBufMut,
+{
+    pub fn new(fd: Fd, buf: B) -> Self {
+        Self { fd, buf, offset: 0, personality: 0 }
+    }
+
+    /// Read at `offset`, `u64::MAX` for the current file position
+    pub fn set_offset(mut self, offset: u64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn set_personality(mut self, personality: &Personality<'_>) -> Self {
+        self.personality = personality.id();
+        self
+    }
+}
+
+// SAFETY: the SQE points into buf, owned and stable, buffer index checked against ring
+unsafe impl<Fd, B> OwnedOp for ReadOwned<Fd, B>
+where
+    Fd: AsFd + 'static,
+    B: IoBufMut,
+{
+    type Output = BufResult<usize, Fd, B>;
+
+    fn build(&mut self, ring: RingId) -> Result<Sqe64> {
+        let mut sqe = IoUringSqe::default();
+        sqe.opcode = IoUringOp::Read;
+        sqe.fd = self.fd.as_fd().as_raw_fd();
+        sqe.off_or_addr2.off = self.offset;
+        sqe.addr_or_splice_off_in.addr = IoUringPtr::new(self.buf.stable_mut_ptr().cast());
+        sqe.personality = self.personality;
+        sqe.len.len = self.buf.bytes_total() as _;
+
+        if let Some(buf_index) = self.buf.buf_index(ring)? {
+            sqe.opcode = IoUringOp::ReadFixed;
+            sqe.buf.buf_index = buf_index;
+        }
+
+        Ok(Sqe64::new(sqe))
+    }
+
+    fn complete(self, res: Result<u32>) -> Self::Output {
+        let Self { fd, mut buf, .. } = self;
+        let result = res.map(|len| {
+            // SAFETY: kernel filled len bytes
+            unsafe { buf.set_init(len as _) };
+            len as usize
+        });
+        BufResult { result, fd, buf }
+    }
+}
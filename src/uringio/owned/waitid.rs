@@ -0,0 +1,83 @@
+use crate::{
+    platform::{
+        iouring::{AsFd, AsRawFd, IoUringOp, IoUringPtr, IoUringSqe, OwnedFd},
+        process::{IdType, IdTypes, Pid, WaitIdOptions},
+    },
+    shared::{
+        error::Result,
+        null::{Null, NULL},
+    },
+    uringio::{
+        operator::process::WaitInfo,
+        owned::{BufResult, OwnedOp},
+        register::personality::Personality,
+        submission::entry::Sqe64,
+        uring::enter::RingId,
+    },
+};
+
+/// WaitIdOwned: `IORING_OP_WAITID` filling an owned [`WaitInfo`], available since kernel 6.7
+///
+/// The pidfd, if any, and the info are handed back on completion, options default to `WEXITED`.
+#[derive(Debug)]
+pub struct WaitIdOwned {
+    id: i32,
+    id_type: IdType,
+    pidfd: Option<OwnedFd>,
+    info: Box<WaitInfo>,
+    options: WaitIdOptions,
+    personality: u16,
+}
+
+impl WaitIdOwned {
+    fn new(id: i32, id_type: IdType, pidfd: Option<OwnedFd>) -> Self {
+        let info = Box::new(WaitInfo::new());
+        Self { id, id_type, pidfd, info, options: WaitIdOptions::EXITED, personality: 0 }
+    }
+
+    /// Wait for any child, `P_ALL`
+    pub fn all() -> Self {
+        Self::new(0, IdTypes::ALL, None)
+    }
+
+    /// Wait for child `pid`, `P_PID`
+    pub fn pid(pid: Pid) -> Self {
+        Self::new(pid, IdTypes::PID, None)
+    }
+
+    /// Wait for the child of `pidfd`, `P_PIDFD`
+    pub fn pidfd(pidfd: OwnedFd) -> Self {
+        Self::new(pidfd.as_fd().as_raw_fd(), IdTypes::PIDFD, Some(pidfd))
+    }
+
+    pub fn set_options(mut self, options: WaitIdOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn set_personality(mut self, personality: &Personality<'_>) -> Self {
+        self.personality = personality.id();
+        self
+    }
+}
+
+// SAFETY: the SQE points into the boxed info, owned and stable
+unsafe impl OwnedOp for WaitIdOwned {
+    type Output = BufResult<Null, Option<OwnedFd>, Box<WaitInfo>>;
+
+    fn build(&mut self, _: RingId) -> Result<Sqe64> {
+        let mut sqe = IoUringSqe::default();
+        sqe.opcode = IoUringOp::Waitid;
+        sqe.fd = self.id;
+        sqe.off_or_addr2.addr2 = IoUringPtr::new((&raw mut *self.info).cast());
+        sqe.len.len = self.id_type as _;
+        sqe.personality = self.personality;
+        sqe.splice_fd_in_or_file_index_or_addr_len.file_index = self.options.bits();
+        Ok(Sqe64::new(sqe))
+    }
+
+    fn complete(self, res: Result<u32>) -> Self::Output {
+        let Self { pidfd, info, .. } = self;
+        BufResult { result: res.map(|_| NULL), fd: pidfd, buf: info }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{
+    platform::iouring::{AsFd, AsRawFd, IoUringOp, IoUringPtr, IoUringSqe},
+    shared::error::Result,
+    uringio::{
+        buf::IoBuf,
+        owned::{BufResult, OwnedOp},
+        register::personality::Personality,
+        submission::entry::Sqe64,
+        uring::enter::RingId,
+    },
+};
+
+/// WriteOwned: `IORING_OP_WRITE`, or `IORING_OP_WRITE_FIXED` for registered buffers
+///
+/// Write the initialized bytes of `buf`.
+#[derive(Debug)]
+pub struct WriteOwned<Fd, B> {
+    fd: Fd,
+    buf: B,
+    offset: u64,
+    personality: u16,
+}
+
+impl<Fd, B> WriteOwned<Fd, B>
+where
+    Fd: AsFd + 'static,
+    B: IoBuf,
+{
+    pub fn new(fd: Fd, buf: B) -> Self {
+        Self { fd, buf, offset: 0, personality: 0 }
+    }
+
+    /// Write at `offset`, `u64::MAX` for the current file position
+    pub fn set_offset(mut self, offset: u64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn set_personality(mut self, personality: &Personality<'_>) -> Self {
+        self.personality = personality.id();
+        self
+    }
+}
+
+// SAFETY: the SQE points into buf, owned and stable, buffer index checked against ring
+unsafe impl<Fd, B> OwnedOp for WriteOwned<Fd, B>
+where
+    Fd: AsFd + 'static,
+    B: IoBuf,
+{
+    type Output = BufResult<usize, Fd, B>;
+
+    fn build(&mut self, ring: RingId) -> Result<Sqe64> {
+        let mut sqe = IoUringSqe::default();
+        sqe.opcode = IoUringOp::Write;
+        sqe.fd = self.fd.as_fd().as_raw_fd();
+        sqe.off_or_addr2.off = self.offset;
+        sqe.addr_or_splice_off_in.addr = IoUringPtr::new(self.buf.stable_ptr().cast_mut().cast());
+        sqe.personality = self.personality;
+        sqe.len.len = self.buf.bytes_init() as _;
+
+        if let Some(buf_index) = self.buf.buf_index(ring)? {
+            sqe.opcode = IoUringOp::WriteFixed;
+            sqe.buf.buf_index = buf_index;
+        }
+
+        Ok(Sqe64::new(sqe))
+    }
+
+    fn complete(self, res: Result<u32>) -> Self::Output {
+        let Self { fd, buf, .. } = self;
+        BufResult { result: res.map(|len| len as _), fd, buf }
+    }
+}
@@ -1,2 +1,13 @@
 pub mod args;
+pub mod buffers;
+pub mod clock;
+pub mod eventfd;
+pub mod files;
+pub mod iowq;
+pub mod napi;
+pub mod pbuf;
+pub mod personality;
+pub mod probe;
+pub mod restrictions;
 pub mod ring_fds;
+pub mod tags;
@@ -1,25 +1,121 @@
-use crate::platform::iouring::{c_void, IoUringRsrcUpdate, IoUringUserData, RawFd};
+use crate::platform::{
+    io::IoVec,
+    iouring::{
+        c_void, IoUringBufStatus, IoUringClockRegister, IoUringFileIndexRange, IoUringFilesUpdate,
+        IoUringParams, IoUringProbe, IoUringPtr, IoUringRestriction, IoUringRsrcFlags,
+        IoUringRsrcRegister, IoUringRsrcUpdate, IoUringRsrcUpdate2, IoUringUserData, RawFd,
+    },
+};
 
 pub trait RegisterArgs {
     fn as_ptr(&self) -> *const c_void;
 }
 
+/// RegisterArgsMut: args written back by the kernel
+pub trait RegisterArgsMut {
+    fn as_mut_ptr(&mut self) -> *mut c_void;
+}
+
 impl RegisterArgs for IoUringRsrcUpdate {
     fn as_ptr(&self) -> *const c_void {
         (&raw const *self).cast()
     }
 }
 
+impl RegisterArgsMut for IoUringRsrcUpdate {
+    fn as_mut_ptr(&mut self) -> *mut c_void {
+        (&raw mut *self).cast()
+    }
+}
+
+impl RegisterArgs for IoUringRsrcRegister {
+    fn as_ptr(&self) -> *const c_void {
+        (&raw const *self).cast()
+    }
+}
+
+impl RegisterArgs for IoUringFilesUpdate {
+    fn as_ptr(&self) -> *const c_void {
+        (&raw const *self).cast()
+    }
+}
+
+impl RegisterArgs for IoUringFileIndexRange {
+    fn as_ptr(&self) -> *const c_void {
+        (&raw const *self).cast()
+    }
+}
+
+impl RegisterArgs for [RawFd] {
+    fn as_ptr(&self) -> *const c_void {
+        <[RawFd]>::as_ptr(self).cast()
+    }
+}
+
+impl RegisterArgs for [u32] {
+    fn as_ptr(&self) -> *const c_void {
+        <[u32]>::as_ptr(self).cast()
+    }
+}
+
+impl RegisterArgsMut for [u32] {
+    fn as_mut_ptr(&mut self) -> *mut c_void {
+        <[u32]>::as_mut_ptr(self).cast()
+    }
+}
+
+impl RegisterArgs for [IoVec] {
+    fn as_ptr(&self) -> *const c_void {
+        <[IoVec]>::as_ptr(self).cast()
+    }
+}
+
+impl RegisterArgs for IoUringClockRegister {
+    fn as_ptr(&self) -> *const c_void {
+        (&raw const *self).cast()
+    }
+}
+
+impl RegisterArgsMut for IoUringProbe {
+    fn as_mut_ptr(&mut self) -> *mut c_void {
+        (&raw mut *self).cast()
+    }
+}
+
+impl RegisterArgs for [IoUringRestriction] {
+    fn as_ptr(&self) -> *const c_void {
+        <[IoUringRestriction]>::as_ptr(self).cast()
+    }
+}
+
+impl RegisterArgs for [IoUringRsrcUpdate] {
+    fn as_ptr(&self) -> *const c_void {
+        <[IoUringRsrcUpdate]>::as_ptr(self).cast()
+    }
+}
+
+impl RegisterArgsMut for [IoUringRsrcUpdate] {
+    fn as_mut_ptr(&mut self) -> *mut c_void {
+        <[IoUringRsrcUpdate]>::as_mut_ptr(self).cast()
+    }
+}
+
 pub trait RegisterRingFd {
     fn new(fd: RawFd) -> Self;
 
+    fn at(fd: RawFd, offset: u32) -> Self;
+
     fn unregister(idx: u32) -> Self;
 }
 
 impl RegisterRingFd for IoUringRsrcUpdate {
     fn new(fd: RawFd) -> Self {
+        Self::at(fd, u32::MAX) // -1U
+    }
+
+    fn at(fd: RawFd, offset: u32) -> Self {
         let mut this = Self::default();
-        this.offset = u32::MAX; // -1U
+        this.offset = offset;
         this.data = unsafe { IoUringUserData::from(fd as u64).ptr };
         this
     }
@@ -30,3 +126,86 @@ impl RegisterRingFd for IoUringRsrcUpdate {
         this
     }
 }
+
+pub trait RegisterSparse {
+    fn sparse(nr: u32) -> Self;
+}
+
+impl RegisterSparse for IoUringRsrcRegister {
+    fn sparse(nr: u32) -> Self {
+        let mut this = Self::default();
+        this.nr = nr;
+        this.flags = IoUringRsrcFlags::REGISTER_SPARSE;
+        this
+    }
+}
+
+impl RegisterArgs for IoUringRsrcUpdate2 {
+    fn as_ptr(&self) -> *const c_void {
+        (&raw const *self).cast()
+    }
+}
+
+pub trait RegisterTagged {
+    fn tagged(data: *const c_void, tags: &[u64]) -> Self;
+}
+
+impl RegisterTagged for IoUringRsrcRegister {
+    fn tagged(data: *const c_void, tags: &[u64]) -> Self {
+        let mut this = Self::default();
+        this.nr = tags.len() as _;
+        this.data = IoUringPtr::new(data.cast_mut());
+        this.tags = IoUringPtr::new(tags.as_ptr().cast_mut().cast());
+        this
+    }
+}
+
+pub trait UpdateTagged {
+    fn new(offset: u32, data: *const c_void, tags: &[u64]) -> Self;
+}
+
+impl UpdateTagged for IoUringRsrcUpdate2 {
+    fn new(offset: u32, data: *const c_void, tags: &[u64]) -> Self {
+        let mut this = Self::default();
+        this.offset = offset;
+        this.data = IoUringPtr::new(data.cast_mut());
+        this.tags = IoUringPtr::new(tags.as_ptr().cast_mut().cast());
+        this.nr = tags.len() as _;
+        this
+    }
+}
+
+pub trait UpdateFiles {
+    fn new(offset: u32, fds: &[RawFd]) -> Self;
+}
+
+impl UpdateFiles for IoUringFilesUpdate {
+    fn new(offset: u32, fds: &[RawFd]) -> Self {
+        let mut this = Self::default();
+        this.offset = offset;
+        this.fds = IoUringPtr::new(fds.as_ptr().cast_mut().cast());
+        this
+    }
+}
+
+pub trait FileAllocRange {
+    fn new(off: u32, len: u32) -> Self;
+}
+
+impl FileAllocRange for IoUringFileIndexRange {
+    fn new(off: u32, len: u32) -> Self {
+        Self { off, len, resv: 0 }
+    }
+}
+
+impl RegisterArgsMut for IoUringParams {
+    fn as_mut_ptr(&mut self) -> *mut c_void {
+        (&raw mut *self).cast()
+    }
+}
+
+impl RegisterArgsMut for IoUringBufStatus {
+    fn as_mut_ptr(&mut self) -> *mut c_void {
+        (&raw mut *self).cast()
+    }
+}
@@ -0,0 +1,240 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Debug,
+    io::{Error, ErrorKind},
+    marker::PhantomData,
+    rc::Rc,
+};
+
+use crate::{
+    platform::{
+        io::IoVec,
+        iouring::IoUringRegisterOp::{RegisterBuffers, UnregisterBuffers},
+    },
+    shared::{
+        error::{err, Result},
+        null::{Null, NULL},
+    },
+    uringio::uring::enter::{RingId, UringEnter},
+};
+
+struct Pool {
+    bufs: Vec<IoVec>,
+    free: RefCell<Vec<u16>>,
+    ring: RingId,
+    registered: Cell<bool>,
+}
+
+impl Drop for Pool {
+    fn drop(&mut self) {
+        if self.registered.get() {
+            // still registered, the kernel may write the buffers until the ring closes
+            return;
+        }
+
+        for iov in &self.bufs {
+            // SAFETY: allocated by Box<[u8]> in BufferPool::register
+            let buf = std::ptr::slice_from_raw_parts_mut(iov.iov_base.cast::<u8>(), iov.iov_len);
+            drop(unsafe { Box::from_raw(buf) });
+        }
+    }
+}
+
+/// BufferPool: registered buffers (`IORING_REGISTER_BUFFERS`) of ring `'fd`, checked out as
+/// [`FixedBuf`]
+///
+/// Buffer memory lives until the pool and every FixedBuf are dropped, and is leaked if the
+/// pool is dropped still registered.
+#[derive(Clone)]
+pub struct BufferPool<'fd> {
+    pool: Rc<Pool>,
+    _marker_: PhantomData<&'fd ()>,
+}
+
+impl<'fd> Debug for BufferPool<'fd> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let free = self.pool.free.borrow().len();
+        f.debug_struct("BufferPool").field("size", &self.size()).field("free", &free).finish()
+    }
+}
+
+impl<'fd> BufferPool<'fd> {
+    /// Register `num` buffers of `size` bytes, available since kernel 5.1
+    pub fn register<S, C, M>(
+        enter: &UringEnter<'fd, S, C, M>,
+        num: u16,
+        size: usize,
+    ) -> Result<Self> {
+        let bufs: Vec<IoVec> = (0..num)
+            .map(|_| {
+                let buf = Box::into_raw(vec![0u8; size].into_boxed_slice());
+                IoVec { iov_base: buf.cast(), iov_len: size }
+            })
+            .collect();
+
+        // drop guard, frees buffers on error
+        let pool = Pool {
+            bufs,
+            free: RefCell::new((0..num).rev().collect()),
+            ring: enter.id(),
+            registered: Cell::new(false),
+        };
+
+        // SAFETY: iovecs point to live buffers
+        unsafe { enter.register(RegisterBuffers, pool.bufs.as_slice(), num as _)? };
+        pool.registered.set(true);
+
+        Ok(Self { pool: Rc::new(pool), _marker_: PhantomData })
+    }
+
+    /// Unregister the buffers, the pool is handed back while another handle or a [`FixedBuf`]
+    /// holds it
+    pub fn unregister<S, C, M>(
+        self,
+        enter: &UringEnter<'fd, S, C, M>,
+    ) -> Result<Null, (Self, Error)> {
+        if enter.id() != self.pool.ring {
+            return Err((self, Error::new(ErrorKind::Other, "Buffer pool of another ring")));
+        }
+
+        if self.in_use() {
+            return Err((self, Error::new(ErrorKind::Other, "Buffer pool in use")));
+        }
+
+        // SAFETY: unregister takes no args
+        if let Err(error) = unsafe { enter.register(UnregisterBuffers, &[] as &[IoVec], 0) } {
+            return Err((self, error));
+        }
+
+        self.pool.registered.set(false);
+        Ok(NULL)
+    }
+
+    /// Another handle or a checked out [`FixedBuf`] holds the pool
+    #[inline]
+    pub fn in_use(&self) -> bool {
+        Rc::strong_count(&self.pool) > 1
+    }
+
+    #[inline]
+    pub fn size(&self) -> u16 {
+        self.pool.bufs.len() as _
+    }
+
+    /// Check out a free buffer, `None` if all are in use
+    pub fn checkout(&self) -> Option<FixedBuf> {
+        let idx = self.pool.free.borrow_mut().pop()?;
+        Some(FixedBuf { pool: self.pool.clone(), idx, init: 0 })
+    }
+}
+
+/// FixedBuf: registered buffer, returned to its pool on drop
+pub struct FixedBuf {
+    pool: Rc<Pool>,
+    idx: u16,
+    init: usize,
+}
+
+impl FixedBuf {
+    #[inline]
+    pub fn buf_index(&self) -> u16 {
+        self.idx
+    }
+
+    /// Check the buffer is registered to `ring`
+    pub(crate) fn check(&self, ring: RingId) -> Result<Null> {
+        if self.pool.ring != ring || !self.pool.registered.get() {
+            return err!("Fixed buffer not registered to the ring");
+        }
+
+        Ok(NULL)
+    }
+
+    #[inline]
+    pub(crate) fn iovec(&self) -> &IoVec {
+        &self.pool.bufs[self.idx as usize]
+    }
+
+    #[inline]
+    pub(crate) fn init(&self) -> usize {
+        self.init
+    }
+
+    /// Set initialized length, clamped to the buffer size
+    #[inline]
+    pub fn set_len(&mut self, len: usize) {
+        self.init = len.min(self.iovec().iov_len);
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: checked out exclusively, init bytes in bound
+        unsafe { std::slice::from_raw_parts(self.iovec().iov_base.cast(), self.init) }
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        let iov = self.iovec();
+        // SAFETY: checked out exclusively, the whole buffer is zero initialized at register
+        unsafe { std::slice::from_raw_parts_mut(iov.iov_base.cast(), iov.iov_len) }
+    }
+}
+
+impl Debug for FixedBuf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FixedBuf").field("idx", &self.idx).field("init", &self.init).finish()
+    }
+}
+
+impl Drop for FixedBuf {
+    fn drop(&mut self) {
+        self.pool.free.borrow_mut().push(self.idx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(ring: RingId) -> BufferPool<'static> {
+        let pool = Pool {
+            bufs: vec![IoVec { iov_base: Box::into_raw(Box::new([0u8; 4])).cast(), iov_len: 4 }],
+            free: RefCell::new(vec![0]),
+            ring,
+            registered: Cell::new(false),
+        };
+        BufferPool { pool: Rc::new(pool), _marker_: PhantomData }
+    }
+
+    #[test]
+    fn test_checkout() {
+        let pool = pool(RingId::next());
+
+        let mut buf = pool.checkout().unwrap();
+        assert!(pool.checkout().is_none());
+        assert!(pool.in_use());
+
+        buf.as_mut_slice().copy_from_slice(b"ring");
+        buf.set_len(8);
+        assert_eq!(buf.as_slice(), b"ring");
+
+        drop(buf);
+        assert!(!pool.in_use());
+        assert_eq!(pool.checkout().unwrap().buf_index(), 0);
+    }
+
+    #[test]
+    fn test_check() {
+        let ring = RingId::next();
+        let pool = pool(ring);
+        let buf = pool.checkout().unwrap();
+        assert!(buf.check(ring).is_err());
+
+        pool.pool.registered.set(true);
+        assert!(buf.check(ring).is_ok());
+        assert!(buf.check(RingId::next()).is_err());
+
+        // not freed while registered
+        pool.pool.registered.set(false);
+    }
+}
@@ -0,0 +1,23 @@
+use crate::{
+    platform::iouring::{IoUringClockRegister, IoUringRegisterOp::RegisterClock},
+    shared::{
+        error::{err, Result},
+        null::{Null, NULL},
+    },
+    uringio::{operator::time::Clock, uring::enter::UringEnter},
+};
+
+impl<'fd, S, C, M> UringEnter<'fd, S, C, M> {
+    /// Run wait timeouts of `io_uring_enter` on `clock`, `Monotonic` or `Boottime`, available
+    /// since kernel 6.12
+    pub fn register_clock(&self, clock: Clock) -> Result<Null> {
+        if clock == Clock::Realtime {
+            return err!("Ring wait clock must be Monotonic or Boottime");
+        }
+
+        let args = IoUringClockRegister { clockid: clock.clock_id() as _, resv: [0; 3] };
+        // SAFETY: args valid during syscall
+        unsafe { self.register(RegisterClock, &args, 0)? };
+        Ok(NULL)
+    }
+}
@@ -0,0 +1,160 @@
+use std::io::{Error, ErrorKind};
+
+use crate::{
+    platform::{
+        epoll::{epoll_ctl, EpollCtlOp, EpollEvent, EpollEventExt, EpollFlags},
+        io::eventfd,
+        iouring::{
+            AsFd, AsRawFd, BorrowedFd,
+            IoUringRegisterOp::{RegisterEventfd, RegisterEventfdAsync, UnregisterEventfd},
+            OwnedFd, RawFd,
+        },
+    },
+    shared::{
+        error::{err, Result},
+        null::{Null, NULL},
+    },
+    uringio::uring::enter::UringEnter,
+};
+
+/// EventFd: eventfd notified on CQE posting (`IORING_REGISTER_EVENTFD`)
+///
+/// Readable while CQEs may be pending, drive the ring from an epoll or mio loop by waiting
+/// on it, [`clear`](Self::clear) it, then collect CQEs. A ring has at most one eventfd.
+#[derive(Debug)]
+pub struct EventFd {
+    fd: OwnedFd,
+}
+
+impl EventFd {
+    /// Create a nonblocking eventfd
+    pub fn new() -> Result<Self> {
+        Ok(Self { fd: eventfd(0)? })
+    }
+
+    /// Register as notifier of every CQE
+    pub fn register<S, C, M>(&self, enter: &UringEnter<'_, S, C, M>) -> Result<Null> {
+        let fds = [self.fd.as_raw_fd()];
+        // SAFETY: fds valid during syscall
+        unsafe { enter.register(RegisterEventfd, fds.as_slice(), 1)? };
+        Ok(NULL)
+    }
+
+    /// Register as notifier of CQEs completed asynchronously only, available since kernel 5.6
+    pub fn register_async<S, C, M>(&self, enter: &UringEnter<'_, S, C, M>) -> Result<Null> {
+        let fds = [self.fd.as_raw_fd()];
+        // SAFETY: fds valid during syscall
+        unsafe { enter.register(RegisterEventfdAsync, fds.as_slice(), 1)? };
+        Ok(NULL)
+    }
+
+    pub fn unregister<S, C, M>(&self, enter: &UringEnter<'_, S, C, M>) -> Result<Null> {
+        // SAFETY: unregister takes no args
+        unsafe { enter.register(UnregisterEventfd, &[] as &[RawFd], 0)? };
+        Ok(NULL)
+    }
+
+    /// Reset the counter, return the number of notifications since last clear
+    ///
+    /// Return 0 if not notified.
+    pub fn clear(&self) -> Result<u64> {
+        let mut count = 0u64;
+        // SAFETY: count valid for 8 bytes
+        let ret =
+            unsafe { libc::read(self.fd.as_raw_fd(), (&raw mut count).cast(), size_of::<u64>()) };
+
+        if ret < 0 {
+            let error = Error::last_os_error();
+            return match error.kind() {
+                ErrorKind::WouldBlock => Ok(0),
+                _ => Err(error),
+            };
+        }
+
+        if ret as usize != size_of::<u64>() {
+            return err!("Short eventfd read");
+        }
+
+        Ok(count)
+    }
+
+    /// Add to `epfd` interest list, readable events carry `data`
+    pub fn epoll_add<Ep>(&self, epfd: &Ep, data: u64) -> Result<Null>
+    where
+        Ep: AsFd,
+    {
+        let event = EpollEvent::new(EpollFlags::IN, data);
+        epoll_ctl(epfd, EpollCtlOp::Add, self, event)?;
+        Ok(NULL)
+    }
+
+    /// Remove from `epfd` interest list
+    pub fn epoll_del<Ep>(&self, epfd: &Ep) -> Result<Null>
+    where
+        Ep: AsFd,
+    {
+        let event = EpollEvent::new(EpollFlags::empty(), 0);
+        epoll_ctl(epfd, EpollCtlOp::Del, self, event)?;
+        Ok(NULL)
+    }
+}
+
+impl AsFd for EventFd {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+}
+
+impl From<EventFd> for OwnedFd {
+    #[inline]
+    fn from(eventfd: EventFd) -> Self {
+        eventfd.fd
+    }
+}
+
+#[cfg(feature = "mio")]
+impl mio::event::Source for EventFd {
+    fn register(
+        &mut self,
+        registry: &mio::Registry,
+        token: mio::Token,
+        interests: mio::Interest,
+    ) -> Result<()> {
+        mio::unix::SourceFd(&self.fd.as_raw_fd()).register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &mio::Registry,
+        token: mio::Token,
+        interests: mio::Interest,
+    ) -> Result<()> {
+        mio::unix::SourceFd(&self.fd.as_raw_fd()).reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &mio::Registry) -> Result<()> {
+        mio::unix::SourceFd(&self.fd.as_raw_fd()).deregister(registry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clear() {
+        let eventfd = EventFd::new().unwrap();
+        assert_eq!(eventfd.clear().unwrap(), 0);
+
+        let count = 2u64;
+        // SAFETY: count valid for 8 bytes
+        let ret = unsafe {
+            libc::write(eventfd.as_fd().as_raw_fd(), (&raw const count).cast(), size_of::<u64>())
+        };
+        assert_eq!(ret, 8);
+
+        assert_eq!(eventfd.clear().unwrap(), 2);
+        assert_eq!(eventfd.clear().unwrap(), 0);
+    }
+}
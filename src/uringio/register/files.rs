@@ -0,0 +1,306 @@
+use std::cell::{Cell, RefCell};
+
+use crate::{
+    platform::iouring::{
+        AsFd, AsRawFd, IoUringFileIndexRange, IoUringFilesUpdate,
+        IoUringRegisterOp::{
+            RegisterFileAllocRange, RegisterFiles, RegisterFiles2, RegisterFilesUpdate,
+            UnregisterFiles,
+        },
+        IoUringRsrcRegister, RawFd,
+    },
+    shared::{
+        error::{err, Result},
+        null::{Null, NULL},
+    },
+    uringio::{
+        operator::fd::FixFd,
+        register::args::{FileAllocRange, RegisterSparse, UpdateFiles},
+        uring::enter::UringEnter,
+    },
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Slot {
+    Free,
+    /// Filled by [`FileTable::register`], no handle taken yet
+    Filled,
+    /// Filled, its only [`FixFd`] handed out
+    Taken,
+}
+
+/// FileTable: registered file table with userspace slot allocator
+///
+/// Slots inside the kernel alloc range (`IORING_REGISTER_FILE_ALLOC_RANGE`) are left to
+/// `IORING_FILE_INDEX_ALLOC` requests, the allocator only hands out slots outside of it.
+///
+/// At most one [`FixFd`] lives per slot, give it back by [`remove`] or [`release`] to reuse the
+/// slot, a dropped handle leaks its slot.
+///
+/// [`remove`]: FileTable::remove
+/// [`release`]: FileTable::release
+#[derive(Debug)]
+pub struct FileTable {
+    slots: RefCell<Vec<Slot>>,
+    alloc_range: Cell<(u32, u32)>,
+    hint: Cell<u32>,
+}
+
+impl FileTable {
+    fn new(slots: Vec<Slot>) -> Self {
+        Self { slots: RefCell::new(slots), alloc_range: Cell::new((0, 0)), hint: Cell::new(0) }
+    }
+
+    /// Register `size` empty slots, available since kernel 5.13
+    pub fn register_sparse<S, C, M>(enter: &UringEnter<'_, S, C, M>, size: u32) -> Result<Self> {
+        #[cfg(feature = "features-checker")]
+        {
+            use crate::platform::iouring::IoUringFeatureFlags;
+            if !enter.features().contains(IoUringFeatureFlags::RSRC_TAGS) {
+                return err!("Feature RSRC_TAGS Invalid");
+            }
+        }
+
+        if size > i32::MAX as u32 {
+            return err!("File table size overflow");
+        }
+
+        let args = IoUringRsrcRegister::sparse(size);
+        let size_of_args = size_of::<IoUringRsrcRegister>() as u32;
+        // SAFETY: sparse register args without data pointer
+        unsafe { enter.register(RegisterFiles2, &args, size_of_args)? };
+
+        Ok(Self::new(vec![Slot::Free; size as usize]))
+    }
+
+    /// Register `fds` at slots `0..fds.len()`
+    pub fn register<S, C, M, Fd>(enter: &UringEnter<'_, S, C, M>, fds: &[Fd]) -> Result<Self>
+    where
+        Fd: AsFd,
+    {
+        if fds.len() > i32::MAX as usize {
+            return err!("File table size overflow");
+        }
+
+        let raw_fds: Vec<RawFd> = fds.iter().map(|fd| fd.as_fd().as_raw_fd()).collect();
+        // SAFETY: raw_fds borrowed from live fds
+        unsafe { enter.register(RegisterFiles, raw_fds.as_slice(), raw_fds.len() as _)? };
+
+        Ok(Self::new(vec![Slot::Filled; fds.len()]))
+    }
+
+    pub fn unregister<S, C, M>(self, enter: &UringEnter<'_, S, C, M>) -> Result<Null> {
+        // SAFETY: unregister takes no args
+        unsafe { enter.register(UnregisterFiles, &[] as &[RawFd], 0)? };
+        Ok(NULL)
+    }
+
+    #[inline]
+    pub fn size(&self) -> u32 {
+        self.slots.borrow().len() as _
+    }
+
+    /// Take the handle of slot `idx` filled by [`FileTable::register`], `None` if out of bound,
+    /// empty or already taken
+    pub fn take(&self, idx: u32) -> Option<FixFd<'_>> {
+        let mut slots = self.slots.borrow_mut();
+        let slot = slots.get_mut(idx as usize).filter(|slot| **slot == Slot::Filled)?;
+        *slot = Slot::Taken;
+        // SAFETY: idx bound checked, slot lives as long as table
+        Some(unsafe { FixFd::new_unchecked(idx) })
+    }
+
+    /// Reserve `offset..offset + len` for `IORING_FILE_INDEX_ALLOC`, available since kernel 6.0
+    pub fn set_alloc_range<S, C, M>(
+        &self,
+        enter: &UringEnter<'_, S, C, M>,
+        offset: u32,
+        len: u32,
+    ) -> Result<Null> {
+        match offset.checked_add(len) {
+            Some(end) if end <= self.size() => {},
+            _ => return err!("File alloc range out of table"),
+        }
+
+        let args = IoUringFileIndexRange::new(offset, len);
+        // SAFETY: args valid during syscall
+        unsafe { enter.register(RegisterFileAllocRange, &args, 0)? };
+
+        self.alloc_range.set((offset, offset + len));
+        Ok(NULL)
+    }
+
+    /// Install `fd` at a free slot outside of the kernel alloc range
+    pub fn install<S, C, M, Fd>(
+        &self,
+        enter: &UringEnter<'_, S, C, M>,
+        fd: &Fd,
+    ) -> Result<FixFd<'_>>
+    where
+        Fd: AsFd,
+    {
+        let Some(idx) = self.alloc() else {
+            return err!("File table is full");
+        };
+
+        self.update(enter, idx, fd.as_fd().as_raw_fd()).inspect_err(|_| self.release_idx(idx))?;
+        // SAFETY: idx allocated in bound
+        Ok(unsafe { FixFd::new_unchecked(idx) })
+    }
+
+    /// Install `fd` at free slot `idx` outside of the kernel alloc range
+    pub fn install_at<S, C, M, Fd>(
+        &self,
+        enter: &UringEnter<'_, S, C, M>,
+        idx: u32,
+        fd: &Fd,
+    ) -> Result<FixFd<'_>>
+    where
+        Fd: AsFd,
+    {
+        self.claim(idx)?;
+        self.update(enter, idx, fd.as_fd().as_raw_fd()).inspect_err(|_| self.release_idx(idx))?;
+        // SAFETY: idx claimed in bound
+        Ok(unsafe { FixFd::new_unchecked(idx) })
+    }
+
+    /// Replace the file at `slot` by `fd`, keep the handle
+    pub fn replace<S, C, M, Fd>(
+        &self,
+        enter: &UringEnter<'_, S, C, M>,
+        slot: &FixFd<'_>,
+        fd: &Fd,
+    ) -> Result<Null>
+    where
+        Fd: AsFd,
+    {
+        self.update(enter, slot.index(), fd.as_fd().as_raw_fd())
+    }
+
+    /// Remove the file at `slot` and free it
+    pub fn remove<S, C, M>(
+        &self,
+        enter: &UringEnter<'_, S, C, M>,
+        slot: FixFd<'_>,
+    ) -> Result<Null> {
+        self.update(enter, slot.index(), -1)?;
+        self.release(slot);
+        Ok(NULL)
+    }
+
+    /// Free `slot` emptied by an operation, e.g. Close on a fixed file
+    #[inline]
+    pub fn release(&self, slot: FixFd<'_>) {
+        self.release_idx(slot.index());
+    }
+
+    fn update<S, C, M>(
+        &self,
+        enter: &UringEnter<'_, S, C, M>,
+        idx: u32,
+        fd: RawFd,
+    ) -> Result<Null> {
+        let fds = [fd];
+        let args = IoUringFilesUpdate::new(idx, &fds);
+        // SAFETY: fds valid during syscall
+        let num = unsafe { enter.register(RegisterFilesUpdate, &args, 1)? };
+
+        if num != 1 {
+            return err!("Failed to update file slot");
+        }
+
+        Ok(NULL)
+    }
+
+    fn alloc(&self) -> Option<u32> {
+        let mut slots = self.slots.borrow_mut();
+        let (start, end) = self.alloc_range.get();
+        let size = slots.len() as u32;
+
+        let hint = self.hint.get();
+        let idx = (hint..size)
+            .chain(0..hint)
+            .find(|&idx| slots[idx as usize] == Slot::Free && !(start..end).contains(&idx))?;
+
+        slots[idx as usize] = Slot::Taken;
+        self.hint.set(idx + 1);
+        Some(idx)
+    }
+
+    fn claim(&self, idx: u32) -> Result<Null> {
+        let mut slots = self.slots.borrow_mut();
+        let (start, end) = self.alloc_range.get();
+
+        let Some(slot) = slots.get_mut(idx as usize) else {
+            return err!("File slot out of table");
+        };
+
+        if (start..end).contains(&idx) {
+            return err!("File slot in alloc range");
+        }
+
+        if *slot != Slot::Free {
+            return err!("File slot in use");
+        }
+
+        *slot = Slot::Taken;
+        Ok(NULL)
+    }
+
+    fn release_idx(&self, idx: u32) {
+        if let Some(slot) = self.slots.borrow_mut().get_mut(idx as usize) {
+            *slot = Slot::Free;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alloc() {
+        let table = FileTable::new(vec![Slot::Free; 4]);
+        table.alloc_range.set((1, 3));
+
+        assert_eq!(table.alloc(), Some(0));
+        assert_eq!(table.alloc(), Some(3));
+        assert_eq!(table.alloc(), None);
+
+        table.release_idx(0);
+        assert_eq!(table.alloc(), Some(0));
+        assert!(table.take(4).is_none());
+    }
+
+    #[test]
+    fn test_claim() {
+        let table = FileTable::new(vec![Slot::Free; 4]);
+        table.alloc_range.set((1, 3));
+
+        assert!(table.claim(1).is_err());
+        assert!(table.claim(2).is_err());
+        assert!(table.claim(4).is_err());
+
+        assert!(table.claim(3).is_ok());
+        assert!(table.claim(3).is_err());
+        assert_eq!(table.alloc(), Some(0));
+
+        table.release_idx(3);
+        assert!(table.claim(3).is_ok());
+    }
+
+    #[test]
+    fn test_take() {
+        let table = FileTable::new(vec![Slot::Filled, Slot::Free]);
+
+        let slot = table.take(0).unwrap();
+        assert_eq!(slot.index(), 0);
+        assert!(table.take(0).is_none());
+        assert!(table.take(1).is_none());
+
+        table.release(slot);
+        assert!(table.take(0).is_none());
+        assert_eq!(table.alloc(), Some(0));
+        assert_eq!(table.alloc(), Some(1));
+    }
+}
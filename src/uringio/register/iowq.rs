@@ -0,0 +1,220 @@
+use std::{
+    fmt::{Debug, Formatter},
+    io::Error,
+    thread::available_parallelism,
+};
+
+use crate::{
+    platform::iouring::{
+        c_void,
+        IoUringRegisterOp::{RegisterIowqAff, RegisterIowqMaxWorkers, UnregisterIowqAff},
+        RawFd,
+    },
+    shared::{
+        error::{err, Result},
+        null::{Null, NULL},
+    },
+    uringio::{register::args::RegisterArgs, uring::enter::UringEnter},
+};
+
+/// CpuSet: CPU affinity mask of io-wq workers
+#[derive(Copy, Clone)]
+pub struct CpuSet {
+    set: libc::cpu_set_t,
+}
+
+impl CpuSet {
+    /// Max CPUs held by a set
+    pub const SIZE: usize = libc::CPU_SETSIZE as usize;
+
+    pub fn new() -> Self {
+        // SAFETY: cpu_set_t is a plain bit array
+        Self { set: unsafe { std::mem::zeroed() } }
+    }
+
+    /// CPUs `0..num`, e.g. `available_parallelism`
+    pub fn first(num: usize) -> Self {
+        (0..num.min(Self::SIZE)).collect()
+    }
+
+    /// Affinity mask of the current thread, what `available_parallelism` counts
+    pub fn current() -> Result<Self> {
+        let mut this = Self::new();
+        // SAFETY: set valid for size_of::<cpu_set_t>()
+        let ret =
+            unsafe { libc::sched_getaffinity(0, size_of::<libc::cpu_set_t>(), &raw mut this.set) };
+        if ret < 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(this)
+    }
+
+    /// Add `cpu`, return false if out of [`SIZE`](Self::SIZE)
+    pub fn insert(&mut self, cpu: usize) -> bool {
+        if cpu >= Self::SIZE {
+            return false;
+        }
+        // SAFETY: cpu in bound
+        unsafe { libc::CPU_SET(cpu, &mut self.set) };
+        true
+    }
+
+    pub fn remove(&mut self, cpu: usize) {
+        if cpu < Self::SIZE {
+            // SAFETY: cpu in bound
+            unsafe { libc::CPU_CLR(cpu, &mut self.set) };
+        }
+    }
+
+    pub fn contains(&self, cpu: usize) -> bool {
+        // SAFETY: cpu in bound
+        cpu < Self::SIZE && unsafe { libc::CPU_ISSET(cpu, &self.set) }
+    }
+
+    pub fn len(&self) -> usize {
+        // SAFETY: set initialized
+        unsafe { libc::CPU_COUNT(&self.set) as _ }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..Self::SIZE).filter(|&cpu| self.contains(cpu))
+    }
+}
+
+impl Default for CpuSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FromIterator<usize> for CpuSet {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let mut this = Self::new();
+        for cpu in iter {
+            this.insert(cpu);
+        }
+        this
+    }
+}
+
+impl Debug for CpuSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl RegisterArgs for CpuSet {
+    fn as_ptr(&self) -> *const c_void {
+        (&raw const self.set).cast()
+    }
+}
+
+/// MaxWorkers: io-wq worker limits per NUMA node (`IORING_REGISTER_IOWQ_MAX_WORKERS`)
+///
+/// Bounded workers run I/O expected to finish in time, e.g. regular files and block devices,
+/// unbounded workers run I/O that may never finish, e.g. sockets. `None` leaves a limit as is.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct MaxWorkers {
+    pub bounded: Option<u32>,
+    pub unbounded: Option<u32>,
+}
+
+impl MaxWorkers {
+    #[inline]
+    pub const fn new() -> Self {
+        Self { bounded: None, unbounded: None }
+    }
+
+    #[inline]
+    pub const fn bounded(mut self, num: u32) -> Self {
+        self.bounded = Some(num);
+        self
+    }
+
+    #[inline]
+    pub const fn unbounded(mut self, num: u32) -> Self {
+        self.unbounded = Some(num);
+        self
+    }
+
+    /// Bound both limits to `available_parallelism`
+    pub fn parallelism() -> Result<Self> {
+        let num = available_parallelism()?.get().min(u32::MAX as usize) as u32;
+        Ok(Self::new().bounded(num).unbounded(num))
+    }
+
+    fn to_raw(self) -> [u32; 2] {
+        [self.bounded.unwrap_or(0), self.unbounded.unwrap_or(0)]
+    }
+
+    fn from_raw([bounded, unbounded]: [u32; 2]) -> Self {
+        Self { bounded: Some(bounded), unbounded: Some(unbounded) }
+    }
+}
+
+impl<'fd, S, C, M> UringEnter<'fd, S, C, M> {
+    /// Run io-wq workers on `cpus` only, available since kernel 5.14
+    pub fn register_iowq_aff(&self, cpus: &CpuSet) -> Result<Null> {
+        if cpus.is_empty() {
+            return err!("Empty io-wq cpu set");
+        }
+
+        // SAFETY: cpus valid during syscall
+        unsafe { self.register(RegisterIowqAff, cpus, size_of::<libc::cpu_set_t>() as _)? };
+        Ok(NULL)
+    }
+
+    /// Restore io-wq workers affinity inherited from the ring creator
+    pub fn unregister_iowq_aff(&self) -> Result<Null> {
+        // SAFETY: unregister takes no args
+        unsafe { self.register(UnregisterIowqAff, &[] as &[RawFd], 0)? };
+        Ok(NULL)
+    }
+
+    /// Set io-wq worker limits, return the previous limits, available since kernel 5.15
+    pub fn register_iowq_max_workers(&self, max: MaxWorkers) -> Result<MaxWorkers> {
+        let mut raw = max.to_raw();
+        // SAFETY: kernel writes back previous limits
+        unsafe { self.register_mut(RegisterIowqMaxWorkers, raw.as_mut_slice(), 2)? };
+        Ok(MaxWorkers::from_raw(raw))
+    }
+
+    /// Current io-wq worker limits
+    #[inline]
+    pub fn iowq_max_workers(&self) -> Result<MaxWorkers> {
+        self.register_iowq_max_workers(MaxWorkers::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpu_set() {
+        let mut cpus = CpuSet::first(2);
+        assert_eq!(cpus.len(), 2);
+        assert!(cpus.contains(1));
+
+        cpus.remove(0);
+        assert!(!cpus.insert(CpuSet::SIZE));
+        assert_eq!(cpus.iter().collect::<Vec<_>>(), [1]);
+
+        assert!(!CpuSet::current().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_max_workers() {
+        let max = MaxWorkers::new().unbounded(8);
+        assert_eq!(max.to_raw(), [0, 8]);
+        assert_eq!(MaxWorkers::from_raw([4, 8]), MaxWorkers::new().bounded(4).unbounded(8));
+    }
+}
@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use crate::{
+    platform::iouring::{
+        c_void, IoUringNapi, IoUringNapiOp, IoUringNapiTracking,
+        IoUringRegisterOp::{RegisterNapi, UnregisterNapi},
+    },
+    shared::{
+        error::Result,
+        null::{Null, NULL},
+    },
+    uringio::{
+        register::args::{RegisterArgs, RegisterArgsMut},
+        uring::enter::UringEnter,
+    },
+};
+
+/// Napi: NAPI busy poll settings of a ring (`IORING_REGISTER_NAPI`)
+///
+/// Receive ops busy poll the device queues of their sockets for up to the timeout. Queues are
+/// tracked from the sockets (`Dynamic`) or added by id (`Static`).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Napi {
+    busy_poll_timeout: Duration,
+    prefer_busy_poll: bool,
+    tracking: IoUringNapiTracking,
+}
+
+impl Default for Napi {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Napi {
+    /// Zero busy poll timeout, dynamic tracking
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            busy_poll_timeout: Duration::ZERO,
+            prefer_busy_poll: false,
+            tracking: IoUringNapiTracking::Dynamic,
+        }
+    }
+
+    /// Busy poll timeout, truncated to microseconds
+    #[inline]
+    pub const fn busy_poll_timeout(mut self, timeout: Duration) -> Self {
+        self.busy_poll_timeout = timeout;
+        self
+    }
+
+    #[inline]
+    pub const fn prefer_busy_poll(mut self, prefer: bool) -> Self {
+        self.prefer_busy_poll = prefer;
+        self
+    }
+
+    /// `Static` tracks NAPI ids added by [`UringEnter::napi_add_id`] only, available since
+    /// kernel 6.13
+    #[inline]
+    pub const fn tracking(mut self, tracking: IoUringNapiTracking) -> Self {
+        self.tracking = tracking;
+        self
+    }
+
+    #[inline]
+    pub const fn get_busy_poll_timeout(&self) -> Duration {
+        self.busy_poll_timeout
+    }
+
+    #[inline]
+    pub const fn get_prefer_busy_poll(&self) -> bool {
+        self.prefer_busy_poll
+    }
+
+    #[inline]
+    pub const fn get_tracking(&self) -> IoUringNapiTracking {
+        self.tracking
+    }
+
+    fn to_raw(self) -> NapiArgs {
+        let mut raw = IoUringNapi::default();
+        raw.busy_poll_to = self.busy_poll_timeout.as_micros().min(u32::MAX as u128) as u32;
+        raw.prefer_busy_poll = self.prefer_busy_poll as u8;
+        raw.opcode = IoUringNapiOp::Register as u8;
+        raw.op_param = self.tracking as u32;
+        NapiArgs(raw)
+    }
+
+    fn from_raw(raw: &IoUringNapi) -> Self {
+        let tracking = match raw.op_param {
+            0 => IoUringNapiTracking::Dynamic,
+            1 => IoUringNapiTracking::Static,
+            _ => IoUringNapiTracking::Inactive,
+        };
+        Self {
+            busy_poll_timeout: Duration::from_micros(raw.busy_poll_to as u64),
+            prefer_busy_poll: raw.prefer_busy_poll != 0,
+            tracking,
+        }
+    }
+}
+
+struct NapiArgs(IoUringNapi);
+
+impl NapiArgs {
+    fn id(opcode: IoUringNapiOp, id: u32) -> Self {
+        let mut raw = IoUringNapi::default();
+        raw.opcode = opcode as u8;
+        raw.op_param = id;
+        Self(raw)
+    }
+}
+
+impl RegisterArgs for NapiArgs {
+    fn as_ptr(&self) -> *const c_void {
+        (&raw const self.0).cast()
+    }
+}
+
+impl RegisterArgsMut for NapiArgs {
+    fn as_mut_ptr(&mut self) -> *mut c_void {
+        (&raw mut self.0).cast()
+    }
+}
+
+impl<'fd, S, C, M> UringEnter<'fd, S, C, M> {
+    /// Enable NAPI busy poll, return the previous settings, available since kernel 6.9
+    pub fn register_napi(&self, napi: Napi) -> Result<Napi> {
+        let mut args = napi.to_raw();
+        // SAFETY: kernel writes back previous settings
+        unsafe { self.register_mut(RegisterNapi, &mut args, 1)? };
+        Ok(Napi::from_raw(&args.0))
+    }
+
+    /// Disable NAPI busy poll, return the previous settings
+    pub fn unregister_napi(&self) -> Result<Napi> {
+        let mut args = NapiArgs(IoUringNapi::default());
+        // SAFETY: kernel writes back previous settings
+        unsafe { self.register_mut(UnregisterNapi, &mut args, 1)? };
+        Ok(Napi::from_raw(&args.0))
+    }
+
+    /// Busy poll NAPI id `id`, e.g. `SO_INCOMING_NAPI_ID` of a socket, requires static tracking,
+    /// available since kernel 6.13
+    pub fn napi_add_id(&self, id: u32) -> Result<Null> {
+        let args = NapiArgs::id(IoUringNapiOp::StaticAddId, id);
+        // SAFETY: args valid during syscall
+        unsafe { self.register(RegisterNapi, &args, 1)? };
+        Ok(NULL)
+    }
+
+    /// Stop busy polling NAPI id `id` added by [`napi_add_id`](UringEnter::napi_add_id),
+    /// available since kernel 6.13
+    pub fn napi_del_id(&self, id: u32) -> Result<Null> {
+        let args = NapiArgs::id(IoUringNapiOp::StaticDelId, id);
+        // SAFETY: args valid during syscall
+        unsafe { self.register(RegisterNapi, &args, 1)? };
+        Ok(NULL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_napi_raw() {
+        let napi = Napi::new()
+            .busy_poll_timeout(Duration::from_micros(50))
+            .prefer_busy_poll(true)
+            .tracking(IoUringNapiTracking::Static);
+        let args = napi.to_raw();
+        assert_eq!(args.0.busy_poll_to, 50);
+        assert_eq!(args.0.op_param, 1);
+        assert_eq!(Napi::from_raw(&args.0), napi);
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::atomic::{AtomicU16, Ordering};
+
+use crate::{
+    platform::iouring::{IoUringBuf, IoUringBufStatus, IoUringRegisterOp::RegisterPbufStatus},
+    shared::error::Result,
+    uringio::uring::enter::UringEnter,
+};
+
+/// PbufStatus: kernel head and published tail of a provided buffer ring
+/// (`IORING_REGISTER_PBUF_STATUS`)
+///
+/// E.g. when multishot recv fails with `ENOBUFS`: no buffer is available, all of them are held
+/// by completions not yet recycled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PbufStatus {
+    pub bgid: u16,
+    pub head: u16,
+    pub tail: u16,
+}
+
+impl PbufStatus {
+    /// Buffers published to the ring the kernel has not picked yet
+    #[inline]
+    pub const fn available(&self) -> u16 {
+        self.tail.wrapping_sub(self.head)
+    }
+
+    /// Buffers out of `buf_cnt` picked by the kernel and not returned to the ring yet
+    #[inline]
+    pub const fn outstanding(&self, buf_cnt: u16) -> u16 {
+        buf_cnt.saturating_sub(self.available())
+    }
+}
+
+impl<'fd, S, C, M> UringEnter<'fd, S, C, M> {
+    /// Kernel head of the buffer ring `bgid`, legacy provided buffers have no head, available
+    /// since kernel 6.8
+    pub fn pbuf_head(&self, bgid: u16) -> Result<u16> {
+        let mut args = IoUringBufStatus { buf_group: bgid as _, ..Default::default() };
+        // SAFETY: kernel writes back the head
+        unsafe { self.register_mut(RegisterPbufStatus, &mut args, 1)? };
+        Ok(args.head as _)
+    }
+
+    /// Status of the buffer ring `bgid` mapped at `ring`, with the tail published in it
+    ///
+    /// # Safety
+    ///
+    /// `ring` must point to the first entry of the buffer ring registered as `bgid`, its tail
+    /// overlays the `resv` field of that entry.
+    pub unsafe fn pbuf_status(&self, bgid: u16, ring: *const IoUringBuf) -> Result<PbufStatus> {
+        let head = self.pbuf_head(bgid)?;
+        let tail = (*(&raw const (*ring).resv).cast::<AtomicU16>()).load(Ordering::Acquire);
+        Ok(PbufStatus { bgid, head, tail })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pbuf_status() {
+        let status = PbufStatus { bgid: 0, head: u16::MAX - 1, tail: 2 };
+        assert_eq!(status.available(), 4);
+        assert_eq!(status.outstanding(8), 4);
+        assert_eq!(status.outstanding(2), 0);
+    }
+}
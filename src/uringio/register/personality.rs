@@ -0,0 +1,56 @@
+use crate::{
+    platform::iouring::{
+        io_uring_register_with, BorrowedFd, IoUringRegisterFlags,
+        IoUringRegisterOp::{RegisterPersonality, UnregisterPersonality},
+        RawFd,
+    },
+    shared::error::{err, Result},
+    uringio::uring::enter::UringEnter,
+};
+
+/// Personality: credentials of the current task registered to a ring
+/// (`IORING_REGISTER_PERSONALITY`), unregistered on drop
+///
+/// Ops set with it run with these credentials instead of the submitter ones, e.g. register
+/// one personality per user after switching the effective uid.
+#[derive(Debug)]
+pub struct Personality<'fd> {
+    ring_fd: BorrowedFd<'fd>,
+    flags: IoUringRegisterFlags,
+    id: u16,
+}
+
+impl<'fd> Personality<'fd> {
+    /// Register the current credentials, available since kernel 5.6
+    pub fn register<S, C, M>(enter: &UringEnter<'fd, S, C, M>) -> Result<Self> {
+        // SAFETY: register personality takes no args
+        let id = unsafe { enter.register(RegisterPersonality, &[] as &[RawFd], 0)? };
+
+        let Ok(id) = u16::try_from(id) else {
+            return err!("Personality id overflow");
+        };
+
+        let (ring_fd, flags) = enter.ring_handle();
+        Ok(Self { ring_fd, flags, id })
+    }
+
+    #[inline]
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+}
+
+impl Drop for Personality<'_> {
+    fn drop(&mut self) {
+        // SAFETY: unregister takes no args, ring fd outlives 'fd
+        let _ = unsafe {
+            io_uring_register_with(
+                self.ring_fd,
+                UnregisterPersonality,
+                self.flags,
+                std::ptr::null(),
+                self.id as _,
+            )
+        };
+    }
+}
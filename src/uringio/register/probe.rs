@@ -0,0 +1,72 @@
+use crate::{
+    platform::iouring::{
+        IoUringOp, IoUringProbe, IoUringProbeOp, IoUringRegisterOp::RegisterProbe,
+    },
+    shared::error::Result,
+    uringio::uring::enter::UringEnter,
+};
+
+/// Probe: opcodes supported by the running kernel (`IORING_REGISTER_PROBE`)
+#[derive(Debug, Clone)]
+pub struct Probe {
+    probe: Box<IoUringProbe>,
+}
+
+impl Probe {
+    /// Probe the kernel, available since kernel 5.6
+    pub fn register<S, C, M>(enter: &UringEnter<'_, S, C, M>) -> Result<Self> {
+        let mut probe = Box::new(IoUringProbe::default());
+        // SAFETY: probe room for OPS_LEN ops, filled by kernel
+        unsafe { enter.register_mut(RegisterProbe, &mut *probe, IoUringProbe::OPS_LEN as _)? };
+        Ok(Self { probe })
+    }
+
+    /// Last opcode known by the kernel, supported or not
+    #[inline]
+    pub fn last_op(&self) -> u8 {
+        self.probe.last_op
+    }
+
+    #[inline]
+    pub fn is_supported(&self, op: IoUringOp) -> bool {
+        self.is_supported_raw(op as u8)
+    }
+
+    pub fn is_supported_raw(&self, op: u8) -> bool {
+        let IoUringProbe { ops_len, ops, .. } = &*self.probe;
+        (op < *ops_len) && (ops[op as usize].flags & IoUringProbeOp::SUPPORTED != 0)
+    }
+
+    /// Supported opcodes in ascending order
+    pub fn supported(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..=u8::MAX).filter(|&op| self.is_supported_raw(op))
+    }
+}
+
+impl From<IoUringProbe> for Probe {
+    #[inline]
+    fn from(probe: IoUringProbe) -> Self {
+        Self { probe: Box::new(probe) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported() {
+        let mut raw = IoUringProbe::default();
+        raw.last_op = 3;
+        raw.ops_len = 3;
+        raw.ops[0].flags = IoUringProbeOp::SUPPORTED;
+        raw.ops[2].flags = IoUringProbeOp::SUPPORTED;
+        // out of ops_len
+        raw.ops[3].flags = IoUringProbeOp::SUPPORTED;
+
+        let probe = Probe::from(raw);
+        assert!(probe.is_supported(IoUringOp::Nop));
+        assert!(!probe.is_supported_raw(1));
+        assert_eq!(probe.supported().collect::<Vec<_>>(), [0, 2]);
+    }
+}
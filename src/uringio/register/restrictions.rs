@@ -0,0 +1,157 @@
+use std::fmt::Debug;
+
+use crate::{
+    platform::iouring::{
+        IoUringOp, IoUringRegisterOp,
+        IoUringRegisterOp::{RegisterEnableRings, RegisterRestrictions},
+        IoUringRestriction, IoUringRestrictionArg, IoUringRestrictionOp, IoUringSetupFlags,
+        IoUringSqeFlags,
+    },
+    shared::{
+        error::{err, Result},
+        null::{Null, NULL},
+    },
+    uringio::uring::{args::UringArgs, Uring},
+};
+
+/// Restrictions: allow list of a disabled ring (`IORING_REGISTER_RESTRICTIONS`)
+///
+/// Register ops and SQE opcodes not allowed are rejected once the ring is enabled, SQE flags
+/// must be a subset of the allowed flags and a superset of the required flags.
+#[derive(Clone, Default)]
+pub struct Restrictions {
+    res: Vec<IoUringRestriction>,
+}
+
+impl Restrictions {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow `io_uring_register` op `op`
+    pub fn register_op(self, op: IoUringRegisterOp) -> Self {
+        let arg = IoUringRestrictionArg { register_op: op };
+        self.push(IoUringRestrictionOp::RegisterOp, arg)
+    }
+
+    /// Allow SQE opcode `op`
+    pub fn sqe_op(self, op: IoUringOp) -> Self {
+        let arg = IoUringRestrictionArg { sqe_op: op };
+        self.push(IoUringRestrictionOp::SqeOp, arg)
+    }
+
+    /// Allow SQE flags `flags`, no flag is allowed by default
+    pub fn sqe_flags_allowed(self, flags: IoUringSqeFlags) -> Self {
+        let arg = IoUringRestrictionArg { sqe_flags: flags };
+        self.push(IoUringRestrictionOp::SqeFlagsAllowed, arg)
+    }
+
+    /// Require SQE flags `flags` on every SQE
+    pub fn sqe_flags_required(self, flags: IoUringSqeFlags) -> Self {
+        let arg = IoUringRestrictionArg { sqe_flags: flags };
+        self.push(IoUringRestrictionOp::SqeFlagsRequired, arg)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.res.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.res.is_empty()
+    }
+
+    fn push(mut self, opcode: IoUringRestrictionOp, arg: IoUringRestrictionArg) -> Self {
+        // non_exhaustive, reserved fields zeroed
+        let mut res = IoUringRestriction::default();
+        res.opcode = opcode;
+        res.register_or_sqe_op_or_sqe_flags = arg;
+        self.res.push(res);
+        self
+    }
+}
+
+impl Debug for Restrictions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let opcodes: Vec<_> = self.res.iter().map(|res| res.opcode).collect();
+        f.debug_struct("Restrictions").field("opcodes", &opcodes).finish()
+    }
+}
+
+/// Disabled: ring set up with `IORING_SETUP_R_DISABLED`
+///
+/// Register resources and restrictions, then [`enable`](Self::enable) the ring, e.g. before
+/// handing it to a less-trusted component. Restrictions can be registered only once.
+#[derive(Debug)]
+pub struct Disabled<'fd, S, C, M> {
+    uring: Uring<'fd, S, C, M>,
+    restricted: bool,
+}
+
+impl<'fd, S, C, M> Disabled<'fd, S, C, M> {
+    pub fn new(uring: Uring<'fd, S, C, M>, args: &UringArgs<S, C, M>) -> Result<Self> {
+        if !args.flags.contains(IoUringSetupFlags::R_DISABLED) {
+            return err!("Require setup flag IORING_SETUP_R_DISABLED");
+        }
+
+        Ok(Self { uring, restricted: false })
+    }
+
+    /// The disabled ring, e.g. register buffers and files with its enter
+    #[inline]
+    pub fn uring(&self) -> &Uring<'fd, S, C, M> {
+        &self.uring
+    }
+
+    #[inline]
+    pub fn is_restricted(&self) -> bool {
+        self.restricted
+    }
+
+    /// Register `res`, available since kernel 5.10
+    pub fn restrict(&mut self, res: &Restrictions) -> Result<Null> {
+        if self.restricted {
+            return err!("Restrictions registered");
+        }
+
+        // SAFETY: restrictions valid during syscall
+        unsafe {
+            self.uring.enter.register(RegisterRestrictions, res.res.as_slice(), res.len() as _)?
+        };
+
+        self.restricted = true;
+        Ok(NULL)
+    }
+
+    /// Enable the ring, start processing SQEs
+    pub fn enable(self) -> Result<Uring<'fd, S, C, M>> {
+        // SAFETY: enable takes no args
+        unsafe { self.uring.enter.register(RegisterEnableRings, &[] as &[IoUringRestriction], 0)? };
+        Ok(self.uring)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restrictions() {
+        let res = Restrictions::new()
+            .register_op(IoUringRegisterOp::RegisterBuffers)
+            .sqe_op(IoUringOp::Read)
+            .sqe_flags_allowed(IoUringSqeFlags::FIXED_FILE)
+            .sqe_flags_required(IoUringSqeFlags::FIXED_FILE);
+        assert_eq!(res.len(), 4);
+
+        let [register, sqe, allowed, required] = res.res.as_slice() else { unreachable!() };
+        assert_eq!(register.opcode, IoUringRestrictionOp::RegisterOp);
+        assert_eq!(sqe.opcode, IoUringRestrictionOp::SqeOp);
+        // SAFETY: union written as sqe_op
+        assert_eq!(unsafe { sqe.register_or_sqe_op_or_sqe_flags.sqe_op }, IoUringOp::Read);
+        assert_eq!(allowed.opcode, IoUringRestrictionOp::SqeFlagsAllowed);
+        assert_eq!(required.opcode, IoUringRestrictionOp::SqeFlagsRequired);
+    }
+}
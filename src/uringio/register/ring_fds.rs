@@ -1,24 +1,75 @@
 use std::io::{Error, ErrorKind, Result};
 
 use crate::{
-    platform::iouring::{
-        io_uring_register, AsRawFd, BorrowedFd, IoUringEnterFlags, IoUringFeatureFlags,
-        IoUringRegisterOp::{RegisterRingFds, UnregisterRingFds},
-        IoUringRsrcUpdate,
+    platform::{
+        iouring::{
+            io_uring_register, io_uring_register_with, AsFd, AsRawFd, BorrowedFd,
+            IoUringEnterFlags, IoUringFeatureFlags,
+            IoUringRegisterOp::{RegisterRingFds, UnregisterRingFds},
+            IoUringRsrcUpdate,
+        },
+        mmap::Mmap,
     },
     shared::null::{Null, NULL},
     uringio::{
         register::args::{RegisterArgs, RegisterRingFd},
-        uring::enter::UringEnter,
+        uring::enter::{ring_handle, UringEnter},
     },
 };
 
+/// Max ring fds per register call (`IO_RINGFD_REG_MAX`)
+pub const RING_FDS_MAX: usize = 16;
+
+/// RegisteredFd: ring created with `IORING_SETUP_REGISTERED_FD_ONLY`, only reachable by its
+/// registered ring index in the creating task, never installed in the fd table
+///
+/// Owns the `IORING_SETUP_NO_MMAP` ring memory, the ring is closed on drop.
+#[derive(Debug)]
+pub struct RegisteredFd {
+    idx: u32,
+    pub(crate) sqes: Mmap,
+    pub(crate) rings: Mmap,
+}
+
+impl RegisteredFd {
+    // Unsafe: idx must be a registered ring index owned by the caller
+    pub(crate) unsafe fn new(idx: u32, sqes: Mmap, rings: Mmap) -> Self {
+        Self { idx, sqes, rings }
+    }
+
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.idx
+    }
+
+    #[inline]
+    pub(crate) fn as_fd(&self) -> BorrowedFd<'_> {
+        // SAFETY: registered index, only used with IORING_ENTER_REGISTERED_RING
+        unsafe { BorrowedFd::borrow_raw(self.idx as _) }
+    }
+}
+
+impl Drop for RegisteredFd {
+    fn drop(&mut self) {
+        let args = IoUringRsrcUpdate::unregister(self.idx);
+        let (fd, flags) = ring_handle(self.as_fd(), true);
+        // SAFETY: args valid during syscall
+        let _ = unsafe { io_uring_register_with(fd, UnregisterRingFds, flags, args.as_ptr(), 1) };
+    }
+}
+
 impl<'fd, S, C, M> UringEnter<'fd, S, C, M> {
     #[inline]
     pub fn is_ring_registered(&self) -> bool {
         self.enter_flags.contains(IoUringEnterFlags::REGISTERED_RING)
     }
 
+    /// Ring created with `IORING_SETUP_REGISTERED_FD_ONLY`
+    #[inline]
+    pub fn is_registered_only(&self) -> bool {
+        self.registered_only
+    }
+
     pub fn register_ring_fd(&mut self) -> Result<Null> {
         #[cfg(feature = "features-checker")]
         {
@@ -31,10 +82,9 @@ impl<'fd, S, C, M> UringEnter<'fd, S, C, M> {
             return Err(Error::new(ErrorKind::Other, "Ring fd registered"));
         }
 
-        #[allow(unused_mut)]
         let mut args = IoUringRsrcUpdate::new(self.enter_fd.as_raw_fd());
-        // SAFETY: asm options !readonly
-        let num = unsafe { io_uring_register(&self.enter_fd, RegisterRingFds, args.as_ptr(), 1)? };
+        // SAFETY: kernel writes back the registered index
+        let num = unsafe { self.register_mut(RegisterRingFds, &mut args, 1)? };
 
         if num != 1 {
             return Err(Error::new(ErrorKind::Other, "Failed to register ring fd"));
@@ -45,10 +95,69 @@ impl<'fd, S, C, M> UringEnter<'fd, S, C, M> {
         Ok(NULL)
     }
 
-    // Unsafe: enter_fd must registered and unregister in drop() function
-    pub(crate) unsafe fn unregister_ring_fd(&mut self) -> Result<u32> {
-        let idx = self.enter_fd.as_raw_fd() as u32;
-        let args = IoUringRsrcUpdate::unregister(idx);
-        Ok(io_uring_register(&self.enter_fd, UnregisterRingFds, args.as_ptr(), 1)?)
+    /// Unregister the ring fd registered by [`register_ring_fd`], enter with the ring fd again
+    ///
+    /// [`register_ring_fd`]: UringEnter::register_ring_fd
+    pub fn unregister_ring_fd(&mut self) -> Result<Null> {
+        if !self.is_ring_registered() {
+            return Err(Error::new(ErrorKind::Other, "Ring fd not registered"));
+        }
+
+        if self.registered_only {
+            return Err(Error::new(ErrorKind::Other, "Registered only ring has no fd"));
+        }
+
+        let args = IoUringRsrcUpdate::unregister(self.enter_fd.as_raw_fd() as u32);
+        // SAFETY: args valid during syscall, ring_fd is the real fd
+        let num = unsafe { io_uring_register(self.ring_fd, UnregisterRingFds, args.as_ptr(), 1)? };
+
+        if num != 1 {
+            return Err(Error::new(ErrorKind::Other, "Failed to unregister ring fd"));
+        }
+
+        self.enter_fd = self.ring_fd;
+        self.enter_flags.remove(IoUringEnterFlags::REGISTERED_RING);
+        Ok(NULL)
+    }
+
+    /// Register ring `fds` to the ring table of the current task, e.g. other rings targeted by
+    /// MsgRing, at `offset..offset + fds.len()`, or at free slots if `offset` is `None`, return
+    /// their registered indices, available since kernel 5.18
+    pub fn register_ring_fds<Fd>(&self, fds: &[Fd], offset: Option<u32>) -> Result<Vec<u32>>
+    where
+        Fd: AsFd,
+    {
+        if fds.len() > RING_FDS_MAX {
+            return Err(Error::new(ErrorKind::Other, "Too many ring fds"));
+        }
+
+        let mut args: Vec<IoUringRsrcUpdate> = fds
+            .iter()
+            .zip(0..)
+            .map(|(fd, i)| {
+                let fd = fd.as_fd().as_raw_fd();
+                match offset {
+                    Some(offset) => IoUringRsrcUpdate::at(fd, offset.saturating_add(i)),
+                    None => IoUringRsrcUpdate::new(fd),
+                }
+            })
+            .collect();
+        let nr = args.len() as _;
+        // SAFETY: kernel writes back the registered indices
+        let num = unsafe { self.register_mut(RegisterRingFds, args.as_mut_slice(), nr)? };
+
+        Ok(args[..num as usize].iter().map(|args| args.offset).collect())
+    }
+
+    /// Unregister ring indices `idxs` of the current task, return the number unregistered
+    pub fn unregister_ring_fds(&self, idxs: &[u32]) -> Result<u32> {
+        if idxs.len() > RING_FDS_MAX {
+            return Err(Error::new(ErrorKind::Other, "Too many ring fds"));
+        }
+
+        let args: Vec<IoUringRsrcUpdate> =
+            idxs.iter().map(|&idx| IoUringRsrcUpdate::unregister(idx)).collect();
+        // SAFETY: args valid during syscall
+        unsafe { self.register(UnregisterRingFds, args.as_slice(), args.len() as _) }
     }
 }
@@ -0,0 +1,443 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    mem, ptr,
+};
+
+use crate::{
+    platform::{
+        io::IoVec,
+        iouring::{
+            c_void, io_uring_register_with, AsFd, AsRawFd, BorrowedFd, IoUringRegisterFlags,
+            IoUringRegisterOp::{
+                self, RegisterBuffers2, RegisterBuffersUpdate, RegisterFiles2,
+                RegisterFilesUpdate2, UnregisterBuffers, UnregisterFiles,
+            },
+            IoUringRsrcRegister, IoUringRsrcUpdate2, RawFd,
+        },
+    },
+    shared::{
+        error::{err, Result},
+        null::{Null, NULL},
+    },
+    uringio::{
+        register::args::{RegisterSparse, RegisterTagged, UpdateTagged},
+        uring::enter::UringEnter,
+    },
+};
+
+/// Bit set in the user_data of tag CQEs, keep op user_data clear of it
+pub const RSRC_TAG: u64 = 1 << 63;
+
+const BUFFER_TAG: u64 = 1 << 62;
+
+const SERIAL_MASK: u64 = BUFFER_TAG - 1;
+
+/// RsrcKind: kind of a tagged resource
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RsrcKind {
+    File,
+    Buffer,
+}
+
+impl RsrcKind {
+    /// Kind of the resource released by a CQE with `user_data`, `None` for op CQEs
+    #[inline]
+    pub const fn from_user_data(user_data: u64) -> Option<Self> {
+        match (user_data & RSRC_TAG != 0, user_data & BUFFER_TAG != 0) {
+            (false, _) => None,
+            (true, false) => Some(Self::File),
+            (true, true) => Some(Self::Buffer),
+        }
+    }
+
+    const fn tag_bits(self) -> u64 {
+        match self {
+            Self::File => RSRC_TAG,
+            Self::Buffer => RSRC_TAG | BUFFER_TAG,
+        }
+    }
+}
+
+/// Released: tagged resource the kernel dropped, mapped back from its tag CQE
+///
+/// A replaced or removed resource stays in use by in-flight ops, the kernel posts its tag CQE
+/// once the last of them completes, only then its memory can be freed.
+#[derive(Debug)]
+pub struct Released<T> {
+    pub kind: RsrcKind,
+    pub index: u32,
+    pub resource: T,
+}
+
+#[derive(Debug)]
+struct Tags<'fd, T> {
+    ring_fd: BorrowedFd<'fd>,
+    flags: IoUringRegisterFlags,
+    registered: Cell<bool>,
+    kind: RsrcKind,
+    size: u32,
+    serial: Cell<u64>,
+    live: RefCell<HashMap<u64, (u32, T)>>,
+}
+
+impl<'fd, T> Tags<'fd, T> {
+    fn new<S, C, M>(enter: &UringEnter<'fd, S, C, M>, kind: RsrcKind, size: u32) -> Self {
+        let (ring_fd, flags) = enter.ring_handle();
+        Self {
+            ring_fd,
+            flags,
+            registered: Cell::new(false),
+            kind,
+            size,
+            serial: Cell::new(0),
+            live: RefCell::new(HashMap::new()),
+        }
+    }
+
+    fn next_tag(&self) -> u64 {
+        let serial = self.serial.get().wrapping_add(1) & SERIAL_MASK;
+        self.serial.set(serial);
+        self.kind.tag_bits() | serial
+    }
+
+    fn insert(&self, tag: u64, idx: u32, res: T) {
+        self.live.borrow_mut().insert(tag, (idx, res));
+    }
+
+    fn release(&self, user_data: u64) -> Option<Released<T>> {
+        if RsrcKind::from_user_data(user_data) != Some(self.kind) {
+            return None;
+        }
+
+        let (index, resource) = self.live.borrow_mut().remove(&user_data)?;
+        Some(Released { kind: self.kind, index, resource })
+    }
+
+    fn pending(&self) -> usize {
+        self.live.borrow().len()
+    }
+
+    fn check_range(&self, idx: u32, nr: usize) -> Result<Null> {
+        match (idx as usize).checked_add(nr) {
+            Some(end) if end <= self.size as usize => Ok(NULL),
+            _ => err!("Resource slot out of table"),
+        }
+    }
+
+    fn register<S, C, M>(
+        &self,
+        enter: &UringEnter<'_, S, C, M>,
+        op: IoUringRegisterOp,
+        data: *const c_void,
+        tags: &[u64],
+    ) -> Result<Null> {
+        if self.size > i32::MAX as u32 {
+            return err!("Resource table size overflow");
+        }
+
+        let args = if tags.is_empty() {
+            IoUringRsrcRegister::sparse(self.size)
+        } else {
+            IoUringRsrcRegister::tagged(data, tags)
+        };
+        let size_of_args = size_of::<IoUringRsrcRegister>() as u32;
+        // SAFETY: data and tags valid during syscall
+        unsafe { enter.register(op, &args, size_of_args)? };
+        self.registered.set(true);
+        Ok(NULL)
+    }
+
+    fn unregister(&self, op: IoUringRegisterOp) -> Result<Null> {
+        if !self.registered.get() {
+            return err!("Resource table not registered");
+        }
+
+        // SAFETY: unregister takes no args, ring fd outlives 'fd
+        unsafe { io_uring_register_with(self.ring_fd, op, self.flags, ptr::null(), 0)? };
+        self.registered.set(false);
+        Ok(NULL)
+    }
+
+    fn update<S, C, M>(
+        &self,
+        enter: &UringEnter<'_, S, C, M>,
+        op: IoUringRegisterOp,
+        idx: u32,
+        data: *const c_void,
+        tag: u64,
+    ) -> Result<Null> {
+        self.check_range(idx, 1)?;
+
+        let tags = [tag];
+        let args = IoUringRsrcUpdate2::new(idx, data, &tags);
+        let size_of_args = size_of::<IoUringRsrcUpdate2>() as u32;
+        // SAFETY: data and tags valid during syscall
+        let num = unsafe { enter.register(op, &args, size_of_args)? };
+
+        if num != 1 {
+            return err!("Failed to update resource slot");
+        }
+
+        Ok(NULL)
+    }
+}
+
+#[cfg(feature = "features-checker")]
+fn check_rsrc_tags<S, C, M>(enter: &UringEnter<'_, S, C, M>) -> Result<Null> {
+    use crate::platform::iouring::IoUringFeatureFlags;
+    if !enter.features().contains(IoUringFeatureFlags::RSRC_TAGS) {
+        return err!("Feature RSRC_TAGS Invalid");
+    }
+    Ok(NULL)
+}
+
+/// TaggedFiles: registered file table (`IORING_REGISTER_FILES2`), each file tagged,
+/// unregistered on drop
+///
+/// Files are kept until the kernel posts their tag CQE, feed CQEs to [`release`] to get them
+/// back. The kernel holds its own file references, files still pending are closed on drop.
+///
+/// [`release`]: TaggedFiles::release
+#[derive(Debug)]
+pub struct TaggedFiles<'fd, T> {
+    tags: Tags<'fd, T>,
+}
+
+impl<'fd, T> TaggedFiles<'fd, T>
+where
+    T: AsFd,
+{
+    /// Register `files` at slots `0..files.len()`, available since kernel 5.13
+    pub fn register<S, C, M>(enter: &UringEnter<'fd, S, C, M>, files: Vec<T>) -> Result<Self> {
+        #[cfg(feature = "features-checker")]
+        check_rsrc_tags(enter)?;
+
+        if files.is_empty() {
+            return err!("Empty file table");
+        }
+
+        let size = files.len().min(u32::MAX as _) as _;
+        let this = Self { tags: Tags::new(enter, RsrcKind::File, size) };
+        let raw_fds: Vec<RawFd> = files.iter().map(|file| file.as_fd().as_raw_fd()).collect();
+        let tags: Vec<u64> = files.iter().map(|_| this.tags.next_tag()).collect();
+        this.tags.register(enter, RegisterFiles2, raw_fds.as_ptr().cast(), &tags)?;
+
+        for ((idx, file), tag) in files.into_iter().enumerate().zip(tags) {
+            this.tags.insert(tag, idx as _, file);
+        }
+        Ok(this)
+    }
+
+    /// Register `size` empty slots
+    pub fn register_sparse<S, C, M>(enter: &UringEnter<'fd, S, C, M>, size: u32) -> Result<Self> {
+        #[cfg(feature = "features-checker")]
+        check_rsrc_tags(enter)?;
+
+        let this = Self { tags: Tags::new(enter, RsrcKind::File, size) };
+        this.tags.register(enter, RegisterFiles2, ptr::null(), &[])?;
+        Ok(this)
+    }
+
+    /// Unregister the table, every file is released by its tag CQE
+    pub fn unregister(&self) -> Result<Null> {
+        self.tags.unregister(UnregisterFiles)
+    }
+
+    /// Install `file` at slot `idx` (`IORING_REGISTER_FILES_UPDATE2`), the previous file is
+    /// released by its tag CQE
+    pub fn update<S, C, M>(
+        &self,
+        enter: &UringEnter<'_, S, C, M>,
+        idx: u32,
+        file: T,
+    ) -> Result<Null> {
+        let fds = [file.as_fd().as_raw_fd()];
+        let tag = self.tags.next_tag();
+        self.tags.update(enter, RegisterFilesUpdate2, idx, fds.as_ptr().cast(), tag)?;
+        self.tags.insert(tag, idx, file);
+        Ok(NULL)
+    }
+
+    /// Empty slot `idx`, the previous file is released by its tag CQE
+    pub fn remove<S, C, M>(&self, enter: &UringEnter<'_, S, C, M>, idx: u32) -> Result<Null> {
+        let fds: [RawFd; 1] = [-1];
+        self.tags.update(enter, RegisterFilesUpdate2, idx, fds.as_ptr().cast(), 0)
+    }
+}
+
+impl<T> TaggedFiles<'_, T> {
+    #[inline]
+    pub fn size(&self) -> u32 {
+        self.tags.size
+    }
+
+    /// Map a CQE `user_data` back to the file released, `None` if not a tag of this table
+    #[inline]
+    pub fn release(&self, user_data: u64) -> Option<Released<T>> {
+        self.tags.release(user_data)
+    }
+
+    /// Files installed or waiting for their tag CQE
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.tags.pending()
+    }
+}
+
+impl<T> Drop for TaggedFiles<'_, T> {
+    fn drop(&mut self) {
+        if self.tags.registered.get() {
+            let _ = self.tags.unregister(UnregisterFiles);
+        }
+    }
+}
+
+/// TaggedBuffers: registered buffer table (`IORING_REGISTER_BUFFERS2`), each buffer tagged,
+/// unregistered on drop
+///
+/// Buffers are boxed so their memory never moves, and kept until the kernel posts their tag
+/// CQE, feed CQEs to [`release`] to get them back. In-flight ops may access a buffer until its
+/// tag CQE, buffers still pending on drop are leaked: [`unregister`] and drain the tag CQEs
+/// first to free them.
+///
+/// [`release`]: TaggedBuffers::release
+/// [`unregister`]: TaggedBuffers::unregister
+#[derive(Debug)]
+pub struct TaggedBuffers<'fd, T> {
+    tags: Tags<'fd, Box<T>>,
+}
+
+fn buffer_iovec<T: AsMut<[u8]>>(buf: &mut T) -> IoVec {
+    let buf = buf.as_mut();
+    IoVec { iov_base: buf.as_mut_ptr().cast(), iov_len: buf.len() }
+}
+
+impl<'fd, T> TaggedBuffers<'fd, T>
+where
+    T: AsMut<[u8]>,
+{
+    /// Register `bufs` at slots `0..bufs.len()`, available since kernel 5.13
+    pub fn register<S, C, M>(enter: &UringEnter<'fd, S, C, M>, bufs: Vec<T>) -> Result<Self> {
+        #[cfg(feature = "features-checker")]
+        check_rsrc_tags(enter)?;
+
+        if bufs.is_empty() {
+            return err!("Empty buffer table");
+        }
+
+        let size = bufs.len().min(u32::MAX as _) as _;
+        let this = Self { tags: Tags::new(enter, RsrcKind::Buffer, size) };
+        let mut bufs: Vec<Box<T>> = bufs.into_iter().map(Box::new).collect();
+        let iovecs: Vec<IoVec> = bufs.iter_mut().map(|buf| buffer_iovec(&mut **buf)).collect();
+        let tags: Vec<u64> = bufs.iter().map(|_| this.tags.next_tag()).collect();
+        this.tags.register(enter, RegisterBuffers2, iovecs.as_ptr().cast(), &tags)?;
+
+        for ((idx, buf), tag) in bufs.into_iter().enumerate().zip(tags) {
+            this.tags.insert(tag, idx as _, buf);
+        }
+        Ok(this)
+    }
+
+    /// Register `size` empty slots
+    pub fn register_sparse<S, C, M>(enter: &UringEnter<'fd, S, C, M>, size: u32) -> Result<Self> {
+        #[cfg(feature = "features-checker")]
+        check_rsrc_tags(enter)?;
+
+        let this = Self { tags: Tags::new(enter, RsrcKind::Buffer, size) };
+        this.tags.register(enter, RegisterBuffers2, ptr::null(), &[])?;
+        Ok(this)
+    }
+
+    /// Unregister the table, every buffer is released by its tag CQE
+    pub fn unregister(&self) -> Result<Null> {
+        self.tags.unregister(UnregisterBuffers)
+    }
+
+    /// Install `buf` at slot `idx` (`IORING_REGISTER_BUFFERS_UPDATE`), the previous buffer is
+    /// released by its tag CQE
+    pub fn update<S, C, M>(
+        &self,
+        enter: &UringEnter<'_, S, C, M>,
+        idx: u32,
+        buf: T,
+    ) -> Result<Null> {
+        let mut buf = Box::new(buf);
+        let iovecs = [buffer_iovec(&mut *buf)];
+        let tag = self.tags.next_tag();
+        self.tags.update(enter, RegisterBuffersUpdate, idx, iovecs.as_ptr().cast(), tag)?;
+        self.tags.insert(tag, idx, buf);
+        Ok(NULL)
+    }
+
+    /// Empty slot `idx`, the previous buffer is released by its tag CQE
+    pub fn remove<S, C, M>(&self, enter: &UringEnter<'_, S, C, M>, idx: u32) -> Result<Null> {
+        let iovecs = [IoVec { iov_base: ptr::null_mut(), iov_len: 0 }];
+        self.tags.update(enter, RegisterBuffersUpdate, idx, iovecs.as_ptr().cast(), 0)
+    }
+}
+
+impl<T> TaggedBuffers<'_, T> {
+    #[inline]
+    pub fn size(&self) -> u32 {
+        self.tags.size
+    }
+
+    /// Map a CQE `user_data` back to the buffer released, `None` if not a tag of this table
+    pub fn release(&self, user_data: u64) -> Option<Released<T>> {
+        let Released { kind, index, resource } = self.tags.release(user_data)?;
+        Some(Released { kind, index, resource: *resource })
+    }
+
+    /// Buffers installed or waiting for their tag CQE
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.tags.pending()
+    }
+}
+
+impl<T> Drop for TaggedBuffers<'_, T> {
+    fn drop(&mut self) {
+        if self.tags.registered.get() {
+            let _ = self.tags.unregister(UnregisterBuffers);
+        }
+
+        // no tag CQE observed after drop, the kernel may still access them
+        for (_, (_, buf)) in self.tags.live.get_mut().drain() {
+            mem::forget(buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags() {
+        let fd = std::fs::File::open("/dev/null").unwrap();
+        let tags = Tags {
+            ring_fd: fd.as_fd(),
+            flags: IoUringRegisterFlags::empty(),
+            registered: Cell::new(false),
+            kind: RsrcKind::Buffer,
+            size: 2,
+            serial: Cell::new(0),
+            live: RefCell::new(HashMap::new()),
+        };
+        let tag = tags.next_tag();
+        assert_eq!(RsrcKind::from_user_data(tag), Some(RsrcKind::Buffer));
+        assert_eq!(RsrcKind::from_user_data(42), None);
+
+        tags.insert(tag, 1, "buf");
+        assert!(tags.release(tag & !BUFFER_TAG).is_none());
+
+        let released = tags.release(tag).unwrap();
+        assert_eq!((released.index, released.resource), (1, "buf"));
+        assert_eq!(tags.pending(), 0);
+
+        assert!(tags.check_range(1, 1).is_ok());
+        assert!(tags.check_range(2, 1).is_err());
+        assert!(tags.unregister(UnregisterBuffers).is_err());
+    }
+}
@@ -0,0 +1,224 @@
+use std::ops::Deref;
+
+use crate::platform::iouring::{IoUringCqe, IoUringCqeFlags, IoUringUserData};
+
+/// Completion: state of the op a CQE belongs to
+#[derive(Debug)]
+pub enum Completion<'r, T> {
+    /// `IORING_CQE_F_MORE` set, the state stays registered for further CQEs
+    More(&'r mut T),
+    /// Final CQE, the state is released
+    Done(T),
+}
+
+// generation 0 never issued, user_data 0 never matches a slot
+const FIRST_GENERATION: u32 = 1;
+
+#[derive(Debug)]
+struct Slot<T> {
+    generation: u32,
+    state: Option<T>,
+}
+
+/// Registry: in-flight op states keyed by user_data token
+///
+/// A token packs the slot index in the low 32 bits and the slot generation in the high
+/// 32 bits, so a late CQE of a released slot never reaches the next state of that slot.
+/// Generations start at 1, a token is never 0, the user_data of SQEs nobody tagged.
+/// States own whatever the kernel accesses (buffers, fds) until the final CQE.
+#[derive(Debug)]
+pub struct Registry<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<u32>,
+    len: usize,
+}
+
+impl<T> Default for Registry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Registry<T> {
+    pub const fn new() -> Self {
+        Self { slots: Vec::new(), free: Vec::new(), len: 0 }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { slots: Vec::with_capacity(capacity), free: Vec::new(), len: 0 }
+    }
+
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Register `state`, the returned token goes to the op user_data
+    pub fn insert(&mut self, state: T) -> IoUringUserData {
+        let idx = match self.free.pop() {
+            Some(idx) => idx,
+            None => {
+                self.slots.push(Slot { generation: FIRST_GENERATION, state: None });
+                (self.slots.len() - 1) as u32
+            },
+        };
+
+        let slot = &mut self.slots[idx as usize];
+        slot.state = Some(state);
+        self.len += 1;
+
+        IoUringUserData::from(Self::token(idx, slot.generation))
+    }
+
+    #[inline]
+    pub fn get(&self, user_data: IoUringUserData) -> Option<&T> {
+        let (idx, generation) = Self::split(user_data);
+        let slot = self.slots.get(idx as usize)?;
+        (slot.generation == generation).then_some(slot.state.as_ref()).flatten()
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, user_data: IoUringUserData) -> Option<&mut T> {
+        let (idx, generation) = Self::split(user_data);
+        let slot = self.slots.get_mut(idx as usize)?;
+        (slot.generation == generation).then_some(slot.state.as_mut()).flatten()
+    }
+
+    /// Release the state of `user_data`, e.g. an op failed to push
+    pub fn remove(&mut self, user_data: IoUringUserData) -> Option<T> {
+        let (idx, generation) = Self::split(user_data);
+        let slot = self.slots.get_mut(idx as usize)?;
+        if slot.generation != generation {
+            return None;
+        }
+
+        let state = slot.state.take()?;
+        slot.generation = match slot.generation.wrapping_add(1) {
+            0 => FIRST_GENERATION,
+            generation => generation,
+        };
+        self.free.push(idx);
+        self.len -= 1;
+        Some(state)
+    }
+
+    /// Look up the state of `cqe`, released unless `IORING_CQE_F_MORE` is set
+    pub fn complete<C>(&mut self, cqe: &C) -> Option<Completion<'_, T>>
+    where
+        C: Deref<Target = IoUringCqe>,
+    {
+        if cqe.flags.contains(IoUringCqeFlags::MORE) {
+            self.get_mut(cqe.user_data).map(Completion::More)
+        } else {
+            self.remove(cqe.user_data).map(Completion::Done)
+        }
+    }
+
+    /// Dispatch CQEs, e.g. a Collector, to `f`, CQEs of unknown tokens are skipped
+    ///
+    /// Return the number of dispatched CQEs.
+    pub fn dispatch<'c, I, C, F>(&mut self, cqes: I, mut f: F) -> usize
+    where
+        I: IntoIterator<Item = &'c C>,
+        C: Deref<Target = IoUringCqe> + 'c,
+        F: FnMut(&'c C, Completion<'_, T>),
+    {
+        let mut num = 0;
+        for cqe in cqes {
+            if let Some(completion) = self.complete(cqe) {
+                f(cqe, completion);
+                num += 1;
+            }
+        }
+        num
+    }
+
+    #[inline]
+    const fn token(idx: u32, generation: u32) -> u64 {
+        ((generation as u64) << 32) | idx as u64
+    }
+
+    #[inline]
+    fn split(user_data: IoUringUserData) -> (u32, u32) {
+        let token = user_data.u64_();
+        (token as u32, (token >> 32) as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uringio::completion::entry::Cqe16;
+
+    fn cqe(user_data: IoUringUserData, flags: IoUringCqeFlags) -> Cqe16 {
+        let mut cqe = Cqe16::default();
+        cqe.user_data = user_data;
+        cqe.flags = flags;
+        cqe
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry::new();
+
+        let a = registry.insert("a");
+        let b = registry.insert("b");
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.get(a), Some(&"a"));
+
+        assert_eq!(registry.remove(a), Some("a"));
+        assert_eq!(registry.remove(a), None);
+
+        // slot reused with a new generation
+        let c = registry.insert("c");
+        assert_ne!(a.u64_(), c.u64_());
+        assert_eq!(registry.get(a), None);
+        assert_eq!(registry.get(b), Some(&"b"));
+    }
+
+    #[test]
+    fn test_zero_user_data() {
+        let mut registry = Registry::new();
+        let a = registry.insert("a");
+        assert_ne!(a.u64_(), 0);
+        let stray = cqe(IoUringUserData::from(0), IoUringCqeFlags::empty());
+        assert!(registry.complete(&stray).is_none());
+        assert_eq!(registry.get(a), Some(&"a"));
+
+        // generation wraps to 1, skipping 0
+        registry.slots[0].generation = u32::MAX;
+        let a = IoUringUserData::from(Registry::<&str>::token(0, u32::MAX));
+        assert_eq!(registry.remove(a), Some("a"));
+        let b = registry.insert("b");
+        assert_eq!(b.u64_(), Registry::<&str>::token(0, 1));
+        assert!(registry.complete(&stray).is_none());
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let mut registry = Registry::new();
+        let token = registry.insert(vec![0u8; 8]);
+
+        let cqes = [
+            cqe(token, IoUringCqeFlags::MORE),
+            cqe(token, IoUringCqeFlags::empty()),
+            cqe(token, IoUringCqeFlags::empty()),
+        ];
+
+        let mut done = Vec::new();
+        let num = registry.dispatch(&cqes, |_, completion| match completion {
+            Completion::More(buf) => buf[0] = 1,
+            Completion::Done(buf) => done.push(buf),
+        });
+
+        assert_eq!(num, 2);
+        assert_eq!(done, [[1, 0, 0, 0, 0, 0, 0, 0]]);
+        assert!(registry.is_empty());
+    }
+}
@@ -0,0 +1,5 @@
+mod executor;
+mod reactor;
+
+pub use executor::{Handle, JoinHandle, OpFuture, Runtime};
+pub use reactor::Drive;
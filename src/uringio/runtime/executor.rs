@@ -0,0 +1,416 @@
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    fmt::{Debug, Formatter},
+    future::Future,
+    pin::{pin, Pin},
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+use crate::{
+    platform::{
+        io::eventfd_blocking,
+        iouring::{AsFd, AsRawFd, IoUringUserData, OwnedFd},
+    },
+    shared::error::Result,
+    uringio::{owned::OwnedOp, runtime::reactor::Drive, runtime::reactor::Reactor, uring::UringIo},
+};
+
+/// Task id of the `block_on` future
+const MAIN_TASK: usize = usize::MAX;
+
+type Task<'fd> = Pin<Box<dyn Future<Output = ()> + 'fd>>;
+
+/// RunQueue: ids of woken tasks, shared with wakers, wakes the runtime thread
+///
+/// The thread is unparked, or woken from a wait in the ring by writing `wake_fd`, whose read
+/// the reactor keeps in flight.
+#[derive(Debug)]
+struct RunQueue {
+    ids: Mutex<VecDeque<usize>>,
+    thread: Thread,
+    wake_fd: OwnedFd,
+    // runtime thread waiting in the ring
+    sleeping: AtomicBool,
+}
+
+impl RunQueue {
+    fn new() -> Result<Self> {
+        Ok(Self {
+            ids: Mutex::new(VecDeque::new()),
+            thread: thread::current(),
+            wake_fd: eventfd_blocking(0)?,
+            sleeping: AtomicBool::new(false),
+        })
+    }
+
+    fn push(&self, id: usize) {
+        if let Ok(mut ids) = self.ids.lock() {
+            ids.push_back(id);
+        }
+        self.thread.unpark();
+
+        if self.sleeping.swap(false, Ordering::SeqCst) {
+            let count = 1u64;
+            // SAFETY: count valid for 8 bytes
+            let _ = unsafe {
+                libc::write(self.wake_fd.as_raw_fd(), (&raw const count).cast(), size_of::<u64>())
+            };
+        }
+    }
+
+    /// Mark the runtime thread waiting in the ring, false if a task was woken meanwhile
+    fn sleep(&self) -> bool {
+        self.sleeping.store(true, Ordering::SeqCst);
+        if self.is_empty() {
+            return true;
+        }
+        self.sleeping.store(false, Ordering::SeqCst);
+        false
+    }
+
+    fn awake(&self) {
+        self.sleeping.store(false, Ordering::SeqCst);
+    }
+
+    fn pop(&self) -> Option<usize> {
+        self.ids.lock().ok()?.pop_front()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.ids.lock().map_or(true, |ids| ids.is_empty())
+    }
+}
+
+struct TaskWaker {
+    id: usize,
+    queue: Arc<RunQueue>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.queue.push(self.id);
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.queue.push(self.id);
+    }
+}
+
+// tasks drop before the reactor, parking their in-flight ops
+struct Shared<'fd, M>
+where
+    M: Drive,
+{
+    tasks: RefCell<Vec<Option<Task<'fd>>>>,
+    queue: Arc<RunQueue>,
+    reactor: RefCell<Reactor<'fd, M>>,
+    // ops dropped while the reactor was borrowed, cancelled on its next turn
+    cancels: RefCell<Vec<IoUringUserData>>,
+}
+
+impl<'fd, M> Shared<'fd, M>
+where
+    M: Drive,
+{
+    fn waker(&self, id: usize) -> Waker {
+        Waker::from(Arc::new(TaskWaker { id, queue: self.queue.clone() }))
+    }
+
+    fn run_task(&self, id: usize) {
+        // taken out while polled, the task may spawn
+        let Some(mut task) = self.tasks.borrow_mut().get_mut(id).and_then(Option::take) else {
+            return;
+        };
+
+        let waker = self.waker(id);
+        if task.as_mut().poll(&mut Context::from_waker(&waker)).is_pending() {
+            self.tasks.borrow_mut()[id] = Some(task);
+        }
+    }
+
+    fn cancel_deferred(&self, reactor: &mut Reactor<'fd, M>) {
+        for user_data in self.cancels.take() {
+            reactor.cancel(user_data);
+        }
+    }
+}
+
+/// Handle: submit ops and spawn tasks on a [`Runtime`]
+pub struct Handle<'fd, M>
+where
+    M: Drive,
+{
+    shared: Rc<Shared<'fd, M>>,
+}
+
+impl<'fd, M> Clone for Handle<'fd, M>
+where
+    M: Drive,
+{
+    fn clone(&self) -> Self {
+        Self { shared: self.shared.clone() }
+    }
+}
+
+impl<'fd, M> Debug for Handle<'fd, M>
+where
+    M: Drive,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let in_flight = self.shared.reactor.borrow().in_flight();
+        f.debug_struct("Handle").field("in_flight", &in_flight).finish()
+    }
+}
+
+impl<'fd, M> Handle<'fd, M>
+where
+    M: Drive,
+{
+    /// Submit `op`, resolved to its output when the CQE arrives
+    pub fn submit<O>(&self, op: O) -> OpFuture<'fd, M, O>
+    where
+        O: OwnedOp,
+    {
+        let state = match self.shared.reactor.borrow_mut().push(op) {
+            Ok(user_data) => OpState::InFlight(user_data),
+            Err((op, err)) => OpState::Ready(Some(op.complete(Err(err)))),
+        };
+
+        OpFuture { shared: self.shared.clone(), state }
+    }
+
+    /// Spawn `future`, polled by [`Runtime::block_on`]
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + 'fd,
+    {
+        let join = Rc::new(RefCell::new(JoinState { output: None, waker: None }));
+
+        let state = join.clone();
+        let task: Task<'fd> = Box::pin(async move {
+            let output = future.await;
+            let mut state = state.borrow_mut();
+            state.output = Some(output);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+
+        let id = {
+            let mut tasks = self.shared.tasks.borrow_mut();
+            match tasks.iter().position(Option::is_none) {
+                Some(id) => {
+                    tasks[id] = Some(task);
+                    id
+                },
+                None => {
+                    tasks.push(Some(task));
+                    tasks.len() - 1
+                },
+            }
+        };
+        self.shared.queue.push(id);
+
+        JoinHandle { state: join }
+    }
+}
+
+enum OpState<T> {
+    InFlight(IoUringUserData),
+    Ready(Option<T>),
+}
+
+/// OpFuture: output of an op submitted to a [`Runtime`]
+///
+/// Dropping it before completion cancels the op (`IORING_OP_ASYNC_CANCEL`), its buffers stay
+/// parked in the reactor until the final CQE.
+pub struct OpFuture<'fd, M, O>
+where
+    M: Drive,
+    O: OwnedOp,
+{
+    shared: Rc<Shared<'fd, M>>,
+    state: OpState<O::Output>,
+}
+
+impl<'fd, M, O> Future for OpFuture<'fd, M, O>
+where
+    M: Drive,
+    O: OwnedOp,
+{
+    type Output = O::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: no field is structurally pinned
+        let this = unsafe { self.get_unchecked_mut() };
+
+        match &mut this.state {
+            OpState::InFlight(user_data) => {
+                let output = match this.shared.reactor.borrow_mut().poll(*user_data, cx) {
+                    Poll::Ready(output) => output,
+                    Poll::Pending => return Poll::Pending,
+                };
+                this.state = OpState::Ready(None);
+                Poll::Ready(*output.downcast::<O::Output>().expect("op type registered"))
+            },
+            OpState::Ready(output) => {
+                Poll::Ready(output.take().expect("OpFuture polled after ready"))
+            },
+        }
+    }
+}
+
+impl<'fd, M, O> Drop for OpFuture<'fd, M, O>
+where
+    M: Drive,
+    O: OwnedOp,
+{
+    fn drop(&mut self) {
+        if let OpState::InFlight(user_data) = self.state {
+            match self.shared.reactor.try_borrow_mut() {
+                Ok(mut reactor) => reactor.cancel(user_data),
+                Err(_) => self.shared.cancels.borrow_mut().push(user_data),
+            }
+        }
+    }
+}
+
+impl<'fd, M, O> Debug for OpFuture<'fd, M, O>
+where
+    M: Drive,
+    O: OwnedOp,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let ready = matches!(self.state, OpState::Ready(_));
+        f.debug_struct("OpFuture").field("ready", &ready).finish()
+    }
+}
+
+struct JoinState<T> {
+    output: Option<T>,
+    waker: Option<Waker>,
+}
+
+/// JoinHandle: output of a spawned task
+pub struct JoinHandle<T> {
+    state: Rc<RefCell<JoinState<T>>>,
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.borrow_mut();
+        match state.output.take() {
+            Some(output) => Poll::Ready(output),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            },
+        }
+    }
+}
+
+impl<T> Debug for JoinHandle<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let finished = self.state.borrow().output.is_some();
+        f.debug_struct("JoinHandle").field("finished", &finished).finish()
+    }
+}
+
+/// Runtime: single-threaded executor driving a [`UringIo`]
+#[derive(Debug)]
+pub struct Runtime<'fd, M>
+where
+    M: Drive,
+{
+    handle: Handle<'fd, M>,
+}
+
+impl<'fd, M> Runtime<'fd, M>
+where
+    M: Drive,
+{
+    pub fn new(uring: UringIo<'fd, M>) -> Result<Self> {
+        let shared = Shared {
+            tasks: RefCell::new(Vec::new()),
+            queue: Arc::new(RunQueue::new()?),
+            reactor: RefCell::new(Reactor::new(uring)),
+            cancels: RefCell::new(Vec::new()),
+        };
+        Ok(Self { handle: Handle { shared: Rc::new(shared) } })
+    }
+
+    #[inline]
+    pub fn handle(&self) -> &Handle<'fd, M> {
+        &self.handle
+    }
+
+    #[inline]
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + 'fd,
+    {
+        self.handle.spawn(future)
+    }
+
+    /// Run `future` and spawned tasks until `future` is ready, on the thread which created the
+    /// runtime, parked or waiting in the ring until woken, also from other threads
+    pub fn block_on<F>(&self, future: F) -> Result<F::Output>
+    where
+        F: Future,
+    {
+        let shared = &self.handle.shared;
+        let mut future = pin!(future);
+        let waker = shared.waker(MAIN_TASK);
+        shared.queue.push(MAIN_TASK);
+
+        loop {
+            while let Some(id) = shared.queue.pop() {
+                if id != MAIN_TASK {
+                    shared.run_task(id);
+                } else if let Poll::Ready(output) =
+                    future.as_mut().poll(&mut Context::from_waker(&waker))
+                {
+                    return Ok(output);
+                }
+            }
+
+            let mut reactor = shared.reactor.borrow_mut();
+            shared.cancel_deferred(&mut reactor);
+            if reactor.in_flight() == 0 {
+                // only wakers from other threads left, unparked by RunQueue::push
+                drop(reactor);
+                if shared.queue.is_empty() {
+                    thread::park();
+                }
+                continue;
+            }
+
+            // without a wake read in flight, turn without waiting
+            let queue = &shared.queue;
+            let wait = reactor.arm_wake(queue.wake_fd.as_fd()) && queue.sleep();
+            let turned = reactor.turn(wait as u32);
+            queue.awake();
+            turned?;
+        }
+    }
+}
+
+impl<'fd, M> Drop for Runtime<'fd, M>
+where
+    M: Drive,
+{
+    fn drop(&mut self) {
+        // break Rc cycles of tasks holding handles, cancel their ops
+        let tasks = self.handle.shared.tasks.take();
+        drop(tasks);
+    }
+}
@@ -0,0 +1,255 @@
+use std::{
+    any::Any,
+    io::{Error, ErrorKind},
+    mem,
+    task::{Context, Poll, Waker},
+};
+
+use crate::{
+    platform::iouring::{
+        AsRawFd, BorrowedFd, IoUringCqeFlags, IoUringOp, IoUringPtr, IoUringSqe, IoUringSqeFlags,
+        IoUringUserData,
+    },
+    shared::error::Result,
+    uringio::{
+        owned::{InFlight, OwnedOp},
+        registry::Registry,
+        submission::{entry::Sqe64, submitter::Submit},
+        uring::{
+            mode::{Iopoll, Mode, Sqpoll},
+            UringIo,
+        },
+    },
+};
+
+/// user_data of async cancel SQEs, never a registry token
+const CANCEL_USER_DATA: u64 = u64::MAX;
+
+/// user_data of the wake read, never a registry token
+const WAKE_USER_DATA: u64 = u64::MAX - 1;
+
+/// Drive: submit SQEs and wait for `wait` CQEs
+pub trait Drive: Mode {
+    /// A read in flight can end a wait, IOPOLL rings only take pollable files
+    const WAKE_READ: bool;
+
+    fn drive(uring: &mut UringIo<'_, Self>, wait: u32) -> Result<u32>;
+}
+
+impl Drive for Sqpoll {
+    const WAKE_READ: bool = true;
+
+    fn drive(uring: &mut UringIo<'_, Self>, wait: u32) -> Result<u32> {
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        submitter.submit();
+        collector.flush(enter, wait)
+    }
+}
+
+impl Drive for Iopoll {
+    const WAKE_READ: bool = false;
+
+    fn drive(uring: &mut UringIo<'_, Self>, wait: u32) -> Result<u32> {
+        let (enter, mut submitter, _) = uring.borrow();
+        submitter.submit(enter, wait)
+    }
+}
+
+struct Slot {
+    op: Option<Box<dyn InFlight>>,
+    output: Option<Box<dyn Any>>,
+    waker: Option<Waker>,
+    orphaned: bool,
+}
+
+/// Reactor: in-flight owned ops of a ring and their wakers
+pub(crate) struct Reactor<'fd, M>
+where
+    M: Drive,
+{
+    uring: UringIo<'fd, M>,
+    slots: Registry<Slot>,
+    in_flight: usize,
+    // eventfd counter read by the wake read, leaked if dropped while armed
+    wake: Box<u64>,
+    wake_armed: bool,
+}
+
+impl<'fd, M> Reactor<'fd, M>
+where
+    M: Drive,
+{
+    pub(crate) fn new(uring: UringIo<'fd, M>) -> Self {
+        Self { uring, slots: Registry::new(), in_flight: 0, wake: Box::new(0), wake_armed: false }
+    }
+
+    #[inline]
+    pub(crate) fn in_flight(&self) -> usize {
+        self.in_flight
+    }
+
+    /// Push `op`, handed back with the error if it fails to build or the submission queue stays
+    /// full after a drive
+    pub(crate) fn push<O>(&mut self, mut op: O) -> Result<IoUringUserData, (O, Error)>
+    where
+        O: OwnedOp,
+    {
+        let mut sqe = match op.build(self.uring.enter.id()) {
+            Ok(sqe) => sqe,
+            Err(err) => return Err((op, err)),
+        };
+        let slot = Slot { op: Some(Box::new(op)), output: None, waker: None, orphaned: false };
+        let user_data = self.slots.insert(slot);
+        sqe.user_data = user_data;
+
+        let sqe = match self.uring.submitter().push(sqe) {
+            Ok(_) => None,
+            Err(sqe) => Some(sqe),
+        };
+
+        if let Some(sqe) = sqe {
+            let pushed =
+                M::drive(&mut self.uring, 0).is_ok() && self.uring.submitter().push(sqe).is_ok();
+            if !pushed {
+                let op = self.slots.remove(user_data).and_then(|slot| slot.op);
+                let op = op.expect("op registered above").into_any();
+                let op = *op.downcast::<O>().expect("op type registered above");
+                let err = Error::new(ErrorKind::WouldBlock, "submission queue is full");
+                return Err((op, err));
+            }
+        }
+
+        self.in_flight += 1;
+        Ok(user_data)
+    }
+
+    /// Poll the output of `user_data`, the slot is released when ready
+    pub(crate) fn poll(
+        &mut self,
+        user_data: IoUringUserData,
+        cx: &mut Context<'_>,
+    ) -> Poll<Box<dyn Any>> {
+        let Some(slot) = self.slots.get_mut(user_data) else {
+            unreachable!("slot released before ready")
+        };
+
+        if slot.output.is_none() {
+            slot.waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        let slot = self.slots.remove(user_data).expect("slot checked above");
+        Poll::Ready(slot.output.expect("output checked above"))
+    }
+
+    /// Drop interest in `user_data`, cancel the op and park its resources until completion
+    pub(crate) fn cancel(&mut self, user_data: IoUringUserData) {
+        let Some(slot) = self.slots.get_mut(user_data) else {
+            return;
+        };
+
+        if slot.op.is_none() {
+            self.slots.remove(user_data);
+            return;
+        }
+
+        slot.orphaned = true;
+        slot.waker = None;
+
+        let mut sqe = IoUringSqe::default();
+        sqe.opcode = IoUringOp::AsyncCancel;
+        sqe.flags = IoUringSqeFlags::CQE_SKIP_SUCCESS;
+        sqe.addr_or_splice_off_in.user_data = user_data;
+        sqe.user_data = IoUringUserData::from(CANCEL_USER_DATA);
+
+        // best effort, a full queue leaves the op running to completion
+        let _ = self.uring.submitter().push(Sqe64::new(sqe));
+    }
+
+    /// Keep a read of eventfd `wake_fd` in flight, a write to it ends a wait in the ring, false
+    /// if the submission queue is full or the mode cannot read it
+    pub(crate) fn arm_wake(&mut self, wake_fd: BorrowedFd<'_>) -> bool {
+        if !M::WAKE_READ {
+            return false;
+        }
+
+        if self.wake_armed {
+            return true;
+        }
+
+        let mut sqe = IoUringSqe::default();
+        sqe.opcode = IoUringOp::Read;
+        sqe.fd = wake_fd.as_raw_fd();
+        sqe.addr_or_splice_off_in.addr = IoUringPtr::new((&raw mut *self.wake).cast());
+        sqe.len.len = size_of::<u64>() as _;
+        sqe.user_data = IoUringUserData::from(WAKE_USER_DATA);
+
+        self.wake_armed = self.uring.submitter().push(Sqe64::new(sqe)).is_ok();
+        self.wake_armed
+    }
+
+    /// Drive the ring and complete ops of collected CQEs
+    pub(crate) fn turn(&mut self, wait: u32) -> Result<usize> {
+        M::drive(&mut self.uring, wait)?;
+
+        let Self { uring, slots, in_flight, wake_armed, .. } = self;
+        let mut collector = uring.collector();
+        collector.update();
+
+        let mut num = 0;
+        for cqe in collector.by_ref() {
+            if cqe.user_data.u64_() == WAKE_USER_DATA {
+                *wake_armed = false;
+                continue;
+            }
+
+            // owned ops are single shot, wait for the final CQE
+            if cqe.flags.contains(IoUringCqeFlags::MORE) {
+                continue;
+            }
+
+            let Some(slot) = slots.get_mut(cqe.user_data) else {
+                continue;
+            };
+            let Some(op) = slot.op.take() else {
+                continue;
+            };
+
+            *in_flight -= 1;
+            num += 1;
+
+            if slot.orphaned {
+                // resources freed after the kernel is done with them
+                drop(slots.remove(cqe.user_data));
+                continue;
+            }
+
+            let res = match cqe.res {
+                res if res < 0 => Err(Error::from_raw_os_error(-res)),
+                res => Ok(res as u32),
+            };
+            slot.output = Some(op.complete(res));
+            if let Some(waker) = slot.waker.take() {
+                waker.wake();
+            }
+        }
+
+        Ok(num)
+    }
+}
+
+impl<'fd, M> Drop for Reactor<'fd, M>
+where
+    M: Drive,
+{
+    fn drop(&mut self) {
+        if self.in_flight > 0 {
+            // kernel may still access in flight buffers
+            mem::forget(mem::take(&mut self.slots));
+        }
+
+        if self.wake_armed {
+            mem::forget(mem::take(&mut self.wake));
+        }
+    }
+}
@@ -8,6 +8,7 @@ use std::{
 
 use crate::{
     platform::iouring::{IoUringSetupFlags, IoUringSqe},
+    squeue,
     uringio::operator::opcode::Opcode,
 };
 
@@ -60,6 +61,17 @@ impl Sqe64 {
     pub const fn new(sqe: IoUringSqe) -> Self {
         Self { raw: sqe }
     }
+
+    /// Convert a legacy [`squeue::Entry`] built by [`opcode`](crate::opcode)
+    ///
+    /// # Safety
+    ///
+    /// Same contract as [`squeue::SubmissionQueue::push`]: buffers and fds referenced by the
+    /// entry must stay valid until it completes, uringio Submitter no longer checks them.
+    pub unsafe fn from_entry(entry: squeue::Entry) -> Self {
+        // SAFETY: both are the 64 bytes io_uring_sqe
+        transmute(entry)
+    }
 }
 
 impl Deref for Sqe64 {
@@ -101,6 +113,18 @@ impl Sqe128 {
         Self { raw, ext_data: [0; 64] }
     }
 
+    /// Convert a legacy [`squeue::Entry128`] built by [`opcode`](crate::opcode)
+    ///
+    /// A SqeMix queue requires the 128 bytes opcode, e.g. `IORING_OP_URING_CMD128`.
+    ///
+    /// # Safety
+    ///
+    /// See [`Sqe64::from_entry`].
+    pub unsafe fn from_entry128(entry: squeue::Entry128) -> Self {
+        // SAFETY: both are the 128 bytes io_uring_sqe
+        transmute(entry)
+    }
+
     #[inline]
     pub fn uring_cmd(&mut self) -> &mut [u8; 80] {
         unsafe { transmute(&mut self.addr3_or_cmd) }
@@ -199,6 +223,22 @@ mod tests {
         assert_eq!(size_of::<SqeMix>(), 64);
     }
 
+    #[test]
+    fn test_from_entry() {
+        use crate::{opcode, platform::iouring::IoUringOp};
+
+        let entry = opcode::Nop::new().build().user_data(0x42);
+        let sqe = unsafe { Sqe64::from_entry(entry) };
+        assert_eq!(sqe.opcode, IoUringOp::Nop);
+        assert_eq!(sqe.user_data.u64_(), 0x42);
+
+        let cmd = [7; 80];
+        let entry = opcode::UringCmd80::new(crate::types::Fd(0), 0x1).cmd(cmd).build();
+        let mut sqe = unsafe { Sqe128::from_entry128(entry) };
+        assert_eq!(sqe.opcode, IoUringOp::UringCmd);
+        assert_eq!(sqe.uring_cmd(), &cmd);
+    }
+
     #[test]
     fn test_sqe_mix_transmute() {
         assert_eq!(size_of::<Sqe64>(), size_of::<SqeMix>());
@@ -12,7 +12,7 @@ use crate::{
     },
     uringio::{
         submission::{index::SubmissionIndex, submitter::Submitter},
-        uring::mode::Mode,
+        uring::{enter::RingId, mode::Mode},
     },
 };
 
@@ -26,12 +26,18 @@ pub struct SubmissionQueue<'fd, S, C, M> {
     pub size: u32,
     pub k_flags: &'fd AtomicU32,
     pub k_dropped: &'fd AtomicU32,
+    pub(crate) ring: RingId,
 
     _marker_: PhantomData<(C, M)>,
 }
 
 impl<'fd, S, C, M> SubmissionQueue<'fd, S, C, M> {
-    pub unsafe fn new(sq_mmap: &Mmap, sqes_mmap: &Mmap, params: &IoUringParams) -> Self {
+    pub unsafe fn new(
+        sq_mmap: &Mmap,
+        sqes_mmap: &Mmap,
+        params: &IoUringParams,
+        ring: RingId,
+    ) -> Self {
         let IoUringParams { sq_off, .. } = params;
 
         let sqes = sqes_mmap.ptr().cast();
@@ -43,7 +49,7 @@ impl<'fd, S, C, M> SubmissionQueue<'fd, S, C, M> {
         let k_dropped = sq_mmap.offset(sq_off.dropped).cast().as_ref();
         SubmissionIndex::setup(sq_mmap, params);
 
-        Self { sqes, k_head, k_tail, mask, size, k_flags, k_dropped, _marker_: PhantomData }
+        Self { sqes, k_head, k_tail, mask, size, k_flags, k_dropped, ring, _marker_: PhantomData }
     }
 
     pub fn flags(&self, order: Ordering) -> IoUringSqFlags {
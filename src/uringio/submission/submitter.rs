@@ -12,12 +12,17 @@ use crate::{
             queue::SubmissionQueue,
         },
         uring::{
-            enter::UringEnter,
+            enter::{RingId, UringEnter},
             mode::{Iopoll, Mode, Sqpoll},
         },
     },
 };
 
+pub(crate) mod private {
+    /// Sealed RingSubmit: Submitter and Guard over it
+    pub trait Sealed {}
+}
+
 /// Submitter
 #[derive(Debug)]
 pub struct Submitter<'s, 'fd, S, C, M>
@@ -86,6 +91,23 @@ pub trait Submit<T> {
     fn push(&mut self, item: T) -> Result<Null, T>;
 }
 
+/// RingSubmit: Submit to a known ring, registered resources of owned ops are checked by its id
+pub trait RingSubmit: Submit<Sqe64> + private::Sealed {
+    fn ring(&self) -> RingId;
+}
+
+impl<'s, 'fd, M> private::Sealed for Submitter<'s, 'fd, Sqe64, Cqe16, M> where M: Mode {}
+
+impl<'s, 'fd, M> RingSubmit for Submitter<'s, 'fd, Sqe64, Cqe16, M>
+where
+    M: Mode,
+{
+    #[inline]
+    fn ring(&self) -> RingId {
+        self.queue.ring
+    }
+}
+
 // Submit to Sqe64 Queue
 impl<'s, 'fd, M> Submit<Sqe64> for Submitter<'s, 'fd, Sqe64, Cqe16, M>
 where
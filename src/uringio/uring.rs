@@ -4,8 +4,10 @@ pub mod feat;
 pub mod mode;
 
 use crate::{
-    platform::iouring::OwnedFd,
-    shared::error::Result,
+    platform::iouring::{
+        IoUringParams, IoUringRegisterOp::RegisterResizeRings, IoUringSetupFlags, OwnedFd,
+    },
+    shared::error::{err, Result},
     uringio::{
         completion::{
             collector::Collector,
@@ -13,6 +15,7 @@ use crate::{
             queue::CompletionQueue,
         },
         mmap_arena::MmapArena,
+        register::ring_fds::RegisteredFd,
         submission::{
             entry::{Sqe, Sqe128, Sqe64, SqeMix},
             queue::SubmissionQueue,
@@ -29,6 +32,8 @@ pub struct Uring<'fd, S, C, M> {
     pub sq: SubmissionQueue<'fd, S, C, M>,
     pub cq: CompletionQueue<'fd, S, C, M>,
     arena: MmapArena<'fd, S, C, M>,
+    // setup flags and fields, the kernel writes back the inherited layout ones only on resize
+    params: IoUringParams,
 }
 
 impl<'fd, S, C, M> Uring<'fd, S, C, M>
@@ -42,9 +47,21 @@ where
             let arena = MmapArena::new(fd, args)?;
 
             let enter = UringEnter::new(fd, args);
-            let sq = SubmissionQueue::new(&arena.sq_mmap, &arena.sqes_mmap, args);
+            let sq = SubmissionQueue::new(&arena.sq_mmap, &arena.sqes_mmap, args, enter.id());
             let cq = CompletionQueue::new(&arena.sq_mmap, &arena.cq_mmap(), args);
-            Ok(Uring { enter, sq, cq, arena })
+            Ok(Uring { enter, sq, cq, arena, params: **args })
+        }
+    }
+
+    /// Ring setup by [`SetupArgs::setup_registered`](args::SetupArgs::setup_registered)
+    pub fn new_registered(fd: &'fd RegisteredFd, args: &UringArgs<S, C, M>) -> Result<Self> {
+        unsafe {
+            let arena = MmapArena::new_registered(fd);
+
+            let enter = UringEnter::new_registered(fd, args);
+            let sq = SubmissionQueue::new(&arena.sq_mmap, &arena.sqes_mmap, args, enter.id());
+            let cq = CompletionQueue::new(&arena.sq_mmap, &arena.cq_mmap(), args);
+            Ok(Uring { enter, sq, cq, arena, params: **args })
         }
     }
 
@@ -53,6 +70,51 @@ where
         Ok(self)
     }
 
+    /// Resize both rings to `sq_entries` and `cq_entries` (`IORING_REGISTER_RESIZE_RINGS`),
+    /// return the new args, available since kernel 6.13
+    ///
+    /// The kernel copies pending SQEs and unreaped CQEs into the new rings, fails with
+    /// `EOVERFLOW` if they do not fit. Requires `IORING_SETUP_DEFER_TASKRUN`, and SQEs
+    /// pending at resize must sit at their identity index. A zero `cq_entries` means twice
+    /// `sq_entries`. The returned args keep the setup flags and SQPOLL fields of the ring, NO_MMAP
+    /// rings are rejected.
+    pub fn resize(&mut self, sq_entries: u32, cq_entries: u32) -> Result<UringArgs<S, C, M>> {
+        if self.enter.is_registered_only() {
+            return err!("Registered only ring memory can not be remapped");
+        }
+        if self.params.flags.contains(IoUringSetupFlags::NO_MMAP) {
+            return err!("User provided ring memory can not be remapped");
+        }
+
+        let mut params = IoUringParams::default();
+        params.sq_entries = sq_entries;
+        if cq_entries != 0 {
+            params.flags |= IoUringSetupFlags::CQSIZE;
+            params.cq_entries = cq_entries;
+        }
+
+        // SAFETY: kernel writes back entries, flags and offsets
+        unsafe { self.enter.register_mut(RegisterResizeRings, &mut params, 1)? };
+        params.flags |= self.params.flags.difference(IoUringSetupFlags::CQSIZE);
+        params.sq_thread_cpu = self.params.sq_thread_cpu;
+        params.sq_thread_idle = self.params.sq_thread_idle;
+        params.wq_fd = self.params.wq_fd;
+        params.features = self.enter.features;
+        let args = UringArgs::from_params(params);
+
+        unsafe {
+            let arena = MmapArena::new(&self.enter.ring_fd, &args)?;
+
+            let ring = self.enter.id();
+            self.sq = SubmissionQueue::new(&arena.sq_mmap, &arena.sqes_mmap, &args, ring);
+            self.cq = CompletionQueue::new(&arena.sq_mmap, &arena.cq_mmap(), &args);
+            // old rings unmapped after the queues moved off them
+            self.arena = arena;
+        }
+        self.params = params;
+        Ok(args)
+    }
+
     pub fn arena(&self) -> &MmapArena<'fd, S, C, M> {
         &self.arena
     }
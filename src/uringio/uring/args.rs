@@ -1,9 +1,18 @@
 use std::{marker::PhantomData, ops::Deref};
 
 use crate::{
-    platform::iouring::{io_uring_setup, AsRawFd, IoUringParams, IoUringSetupFlags, OwnedFd},
-    shared::error::Result,
-    uringio::{completion::entry::Cqe, submission::entry::Sqe, uring::mode::Mode},
+    platform::{
+        iouring::{
+            io_uring_setup, AsRawFd, IntoRawFd, IoUringParams, IoUringPtr, IoUringSetupFlags,
+            OwnedFd,
+        },
+        mmap::Mmap,
+    },
+    shared::error::{err, Result},
+    uringio::{
+        completion::entry::Cqe, register::ring_fds::RegisteredFd, submission::entry::Sqe,
+        uring::mode::Mode,
+    },
 };
 
 #[derive(Debug)]
@@ -110,7 +119,7 @@ where
         self
     }
 
-    // Must use with NO_MMAP
+    // Must use with NO_MMAP, setup with setup_registered
     pub fn registered_fd_only(mut self) -> Self {
         self.params.flags |= IoUringSetupFlags::REGISTERED_FD_ONLY;
         self
@@ -129,6 +138,11 @@ where
 
     pub fn setup(self) -> Result<(OwnedFd, UringArgs<S, C, M>)> {
         let Self { mut params, .. } = self;
+
+        if params.flags.contains(IoUringSetupFlags::REGISTERED_FD_ONLY) {
+            return err!("Registered fd only ring, use setup_registered");
+        }
+
         let fd = unsafe { io_uring_setup(params.sq_entries, &mut params)? };
 
         #[cfg(feature = "features-checker")]
@@ -137,17 +151,71 @@ where
             check_setup_features(params.features)?;
         }
 
-        let args = UringArgs { params, _marker_: PhantomData };
-        Ok((fd, args))
+        Ok((fd, UringArgs::from_params(params)))
+    }
+
+    /// Setup with `IORING_SETUP_REGISTERED_FD_ONLY`, the ring is only reachable by the
+    /// returned registered index, available since kernel 6.5
+    ///
+    /// Ring memory is allocated here (`IORING_SETUP_NO_MMAP`). Before kernel 6.13 each of SQEs
+    /// and rings must fit in a page.
+    pub fn setup_registered(self) -> Result<(RegisteredFd, UringArgs<S, C, M>)> {
+        let Self { mut params, .. } = self.no_mmap().registered_fd_only();
+
+        let (sqes_size, rings_size) = user_mem_size::<S, C>(&params);
+        let sqes = Mmap::anonymous(sqes_size)?;
+        let rings = Mmap::anonymous(rings_size)?;
+        params.sq_off.user_addr = IoUringPtr::new(sqes.ptr().as_ptr());
+        params.cq_off.user_addr = IoUringPtr::new(rings.ptr().as_ptr());
+
+        // returns the registered index instead of an fd
+        let idx = unsafe { io_uring_setup(params.sq_entries, &mut params)? }.into_raw_fd();
+
+        #[cfg(feature = "features-checker")]
+        {
+            use crate::uringio::uring::feat::check_setup_features;
+            check_setup_features(params.features)?;
+        }
+
+        // SAFETY: idx registered by setup and owned here
+        let fd = unsafe { RegisteredFd::new(idx as _, sqes, rings) };
+        Ok((fd, UringArgs::from_params(params)))
     }
 }
 
+// struct io_rings before cqes, cache line aligned, 2 lines at most
+const RINGS_HEADER_SIZE: usize = 128;
+
+// Upper bound of the kernel NO_MMAP layout: SQEs, and ring header, CQEs and SQ array
+fn user_mem_size<S: Sqe, C: Cqe>(params: &IoUringParams) -> (usize, usize) {
+    let sq_entries = params.sq_entries.next_power_of_two() as usize;
+    let cq_entries = if params.flags.contains(IoUringSetupFlags::CQSIZE) {
+        params.cq_entries.next_power_of_two() as usize
+    } else {
+        sq_entries * 2
+    };
+
+    let sqes_size = sq_entries * S::SETUP_SQE_SIZE;
+    let mut rings_size = RINGS_HEADER_SIZE + cq_entries * C::SETUP_CQE_SIZE;
+    if !params.flags.contains(IoUringSetupFlags::NO_SQARRAY) {
+        rings_size = rings_size.next_multiple_of(RINGS_HEADER_SIZE);
+        rings_size += sq_entries * size_of::<u32>();
+    }
+    (sqes_size, rings_size)
+}
+
 #[derive(Debug)]
 pub struct UringArgs<S, C, M> {
     params: IoUringParams,
     _marker_: PhantomData<(S, C, M)>,
 }
 
+impl<S, C, M> UringArgs<S, C, M> {
+    pub(crate) fn from_params(params: IoUringParams) -> Self {
+        UringArgs { params, _marker_: PhantomData }
+    }
+}
+
 impl<S, C, M> UringArgs<S, C, M>
 where
     S: Sqe,
@@ -1,15 +1,57 @@
-use std::{io::Result, marker::PhantomData};
+use std::{
+    io::Result,
+    marker::PhantomData,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 use crate::{
     platform::iouring::{
-        io_uring_enter, AsFd, BorrowedFd, IoUringEnterFlags, IoUringFeatureFlags, OwnedFd,
+        io_uring_enter, io_uring_register, io_uring_register_with, AsFd, BorrowedFd,
+        IoUringEnterFlags, IoUringFeatureFlags, IoUringRegisterFlags, IoUringRegisterOp, OwnedFd,
+    },
+    uringio::{
+        register::{
+            args::{RegisterArgs, RegisterArgsMut},
+            ring_fds::RegisteredFd,
+        },
+        uring::{args::UringArgs, mode::Mode},
     },
-    uringio::uring::{args::UringArgs, mode::Mode},
 };
 
+/// RingId: process unique identity of a ring, checked against resources registered to it
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct RingId(u64);
+
+impl RingId {
+    pub(crate) fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+// Ring handle of register calls outliving an enter borrow, registered only rings have no fd
+// and go by their registered index
+#[inline]
+pub(crate) fn ring_handle(
+    ring_fd: BorrowedFd<'_>,
+    registered_only: bool,
+) -> (BorrowedFd<'_>, IoUringRegisterFlags) {
+    let flags = if registered_only {
+        IoUringRegisterFlags::USE_REGISTERED_RING
+    } else {
+        IoUringRegisterFlags::empty()
+    };
+    (ring_fd, flags)
+}
+
 #[derive(Debug)]
 pub struct UringEnter<'fd, S, C, M> {
     pub(crate) enter_fd: BorrowedFd<'fd>,
+    // ring fd, enter_fd may be a registered ring index
+    pub(crate) ring_fd: BorrowedFd<'fd>,
+    // IORING_SETUP_REGISTERED_FD_ONLY, ring_fd is the registered index too
+    pub(crate) registered_only: bool,
+    pub(crate) id: RingId,
     // TODO: init flags
     pub(crate) enter_flags: IoUringEnterFlags,
     pub(crate) features: IoUringFeatureFlags,
@@ -24,14 +66,39 @@ where
     pub fn new(fd: &'fd OwnedFd, args: &UringArgs<S, C, M>) -> Self {
         Self {
             enter_fd: fd.as_fd(),
+            ring_fd: fd.as_fd(),
+            registered_only: false,
+            id: RingId::next(),
             enter_flags: M::ENTER_FLAG,
             features: args.features,
             _marker_: PhantomData,
         }
     }
+
+    pub fn new_registered(fd: &'fd RegisteredFd, args: &UringArgs<S, C, M>) -> Self {
+        Self {
+            enter_fd: fd.as_fd(),
+            ring_fd: fd.as_fd(),
+            registered_only: true,
+            id: RingId::next(),
+            enter_flags: M::ENTER_FLAG | IoUringEnterFlags::REGISTERED_RING,
+            features: args.features,
+            _marker_: PhantomData,
+        }
+    }
 }
 
 impl<'fd, S, C, M> UringEnter<'fd, S, C, M> {
+    #[inline]
+    pub fn id(&self) -> RingId {
+        self.id
+    }
+
+    #[inline]
+    pub(crate) fn ring_handle(&self) -> (BorrowedFd<'fd>, IoUringRegisterFlags) {
+        ring_handle(self.ring_fd, self.registered_only)
+    }
+
     #[inline]
     pub fn features(&self) -> &IoUringFeatureFlags {
         &self.features
@@ -63,14 +130,45 @@ impl<'fd, S, C, M> UringEnter<'fd, S, C, M> {
             io_uring_enter(self.enter_fd, to_submit, min_complete, self.enter_flags | flags)?
         })
     }
+
+    // Unsafe: args must match register op and stay valid during syscall
+    pub(crate) unsafe fn register<A>(&self, op: IoUringRegisterOp, args: &A, nr: u32) -> Result<u32>
+    where
+        A: RegisterArgs + ?Sized,
+    {
+        if self.is_ring_registered() {
+            let flags = IoUringRegisterFlags::USE_REGISTERED_RING;
+            Ok(io_uring_register_with(self.enter_fd, op, flags, args.as_ptr(), nr)?)
+        } else {
+            Ok(io_uring_register(self.enter_fd, op, args.as_ptr(), nr)?)
+        }
+    }
+
+    // Unsafe: args must match register op and stay valid during syscall, kernel writes back args
+    pub(crate) unsafe fn register_mut<A>(
+        &self,
+        op: IoUringRegisterOp,
+        args: &mut A,
+        nr: u32,
+    ) -> Result<u32>
+    where
+        A: RegisterArgsMut + ?Sized,
+    {
+        let ptr = args.as_mut_ptr().cast_const();
+        if self.is_ring_registered() {
+            let flags = IoUringRegisterFlags::USE_REGISTERED_RING;
+            Ok(io_uring_register_with(self.enter_fd, op, flags, ptr, nr)?)
+        } else {
+            Ok(io_uring_register(self.enter_fd, op, ptr, nr)?)
+        }
+    }
 }
 
 impl<'fd, S, C, M> Drop for UringEnter<'fd, S, C, M> {
     fn drop(&mut self) {
-        if self.is_ring_registered() {
-            unsafe {
-                let _ = self.unregister_ring_fd();
-            }
+        // registered only rings are unregistered by RegisteredFd
+        if self.is_ring_registered() && !self.registered_only {
+            let _ = self.unregister_ring_fd();
         }
     }
 }
@@ -1,11 +1,27 @@
-use std::io::{Error, ErrorKind};
+use std::{
+    error,
+    fmt::{Debug, Display, Formatter},
+    io::{Error, ErrorKind},
+};
 
 use crate::{
-    platform::iouring::IoUringFeatureFlags,
+    platform::iouring::{IoUringFeatureFlags, IoUringOp, IoUringSetupFlags},
     shared::{
         error::Result,
         null::{Null, NULL},
     },
+    uringio::{
+        operator::Op,
+        register::probe::Probe,
+        submission::{
+            entry::{Sqe128, Sqe64, SqeMix},
+            submitter::{private, RingSubmit, Submit},
+        },
+        uring::{
+            args::UringArgs,
+            enter::{RingId, UringEnter},
+        },
+    },
 };
 
 pub fn check_setup_features(features: IoUringFeatureFlags) -> Result<Null> {
@@ -15,3 +31,273 @@ pub fn check_setup_features(features: IoUringFeatureFlags) -> Result<Null> {
 
     Ok(NULL)
 }
+
+/// Capabilities: opcodes, features and setup flags of a ring
+#[derive(Debug, Clone)]
+pub struct Capabilities {
+    probe: Probe,
+    features: IoUringFeatureFlags,
+    setup_flags: IoUringSetupFlags,
+}
+
+impl Capabilities {
+    /// Probe the ring, available since kernel 5.6
+    pub fn new<S, C, M>(
+        enter: &UringEnter<'_, S, C, M>,
+        args: &UringArgs<S, C, M>,
+    ) -> Result<Self> {
+        let probe = Probe::register(enter)?;
+        Ok(Self::from_parts(probe, args.features, args.flags))
+    }
+
+    #[inline]
+    pub fn from_parts(
+        probe: Probe,
+        features: IoUringFeatureFlags,
+        setup_flags: IoUringSetupFlags,
+    ) -> Self {
+        Self { probe, features, setup_flags }
+    }
+
+    #[inline]
+    pub fn probe(&self) -> &Probe {
+        &self.probe
+    }
+
+    #[inline]
+    pub fn features(&self) -> IoUringFeatureFlags {
+        self.features
+    }
+
+    #[inline]
+    pub fn setup_flags(&self) -> IoUringSetupFlags {
+        self.setup_flags
+    }
+
+    #[inline]
+    pub fn is_supported(&self, op: IoUringOp) -> bool {
+        self.probe.is_supported(op)
+    }
+
+    #[inline]
+    pub fn has_feature(&self, feature: IoUringFeatureFlags) -> bool {
+        self.features.contains(feature)
+    }
+
+    #[inline]
+    pub fn has_setup_flag(&self, flag: IoUringSetupFlags) -> bool {
+        self.setup_flags.contains(flag)
+    }
+
+    /// Wrap `submitter`, pushes are checked against the probe under `features-checker`
+    #[inline]
+    pub fn guard<Sub>(&self, submitter: Sub) -> Guard<'_, Sub> {
+        Guard { caps: self, submitter }
+    }
+}
+
+/// EntryOpcode: opcode in the built SQE, checked by [`Guard`]
+///
+/// May differ from [`Op::OP_CODE`], e.g. `UringCmd80::mixed` or an owned read of a registered
+/// buffer.
+pub trait EntryOpcode {
+    fn entry_opcode(&self) -> u8;
+}
+
+impl<T> EntryOpcode for T
+where
+    T: Op,
+{
+    #[inline]
+    fn entry_opcode(&self) -> u8 {
+        // SAFETY: Op laid out as its entry, opcode at offset 0
+        unsafe { (&raw const *self).cast::<u8>().read() }
+    }
+}
+
+macro_rules! impl_entry_opcode {
+    ($($sqe:ty),*) => {$(
+        impl EntryOpcode for $sqe {
+            #[inline]
+            fn entry_opcode(&self) -> u8 {
+                self.opcode as u8
+            }
+        }
+    )*};
+}
+
+impl_entry_opcode!(Sqe64, Sqe128, SqeMix);
+
+/// PushError: op rejected by [`Guard::push`], handed back
+pub enum PushError<T> {
+    /// Submission queue full
+    Full(T),
+    /// Opcode not supported by the kernel
+    Unsupported(T),
+}
+
+impl<T> PushError<T> {
+    #[inline]
+    pub fn into_inner(self) -> T {
+        match self {
+            Self::Full(op) | Self::Unsupported(op) => op,
+        }
+    }
+}
+
+impl<T> Debug for PushError<T>
+where
+    T: EntryOpcode,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Full(op) => f.debug_tuple("Full").field(&op.entry_opcode()).finish(),
+            Self::Unsupported(op) => {
+                f.debug_tuple("Unsupported").field(&op.entry_opcode()).finish()
+            },
+        }
+    }
+}
+
+impl<T> Display for PushError<T>
+where
+    T: EntryOpcode,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Full(op) => write!(f, "Submission queue full, opcode {}", op.entry_opcode()),
+            Self::Unsupported(op) => {
+                write!(f, "Opcode {} unsupported by kernel", op.entry_opcode())
+            },
+        }
+    }
+}
+
+impl<T> error::Error for PushError<T> where T: EntryOpcode {}
+
+impl<T> From<PushError<T>> for Error
+where
+    T: EntryOpcode,
+{
+    fn from(err: PushError<T>) -> Self {
+        let kind = match err {
+            PushError::Full(_) => ErrorKind::WouldBlock,
+            PushError::Unsupported(_) => ErrorKind::Unsupported,
+        };
+        Error::new(kind, err.to_string())
+    }
+}
+
+/// Guard: submitter checking ops against [`Capabilities`]
+#[derive(Debug)]
+pub struct Guard<'c, Sub> {
+    #[cfg_attr(not(feature = "features-checker"), allow(dead_code))]
+    caps: &'c Capabilities,
+    submitter: Sub,
+}
+
+impl<'c, Sub> Guard<'c, Sub> {
+    /// Push `op`, checked by the opcode in the built entry
+    pub fn push<T>(&mut self, op: T) -> Result<Null, PushError<T>>
+    where
+        T: EntryOpcode,
+        Sub: Submit<T>,
+    {
+        #[cfg(feature = "features-checker")]
+        {
+            if !self.caps.probe.is_supported_raw(op.entry_opcode()) {
+                return Err(PushError::Unsupported(op));
+            }
+        }
+
+        self.submitter.push(op).map_err(PushError::Full)
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> Sub {
+        self.submitter
+    }
+}
+
+// e.g. owned ops pushed by a Driver are checked on their built Sqe64
+impl<'c, Sub, T> Submit<T> for Guard<'c, Sub>
+where
+    T: EntryOpcode,
+    Sub: Submit<T>,
+{
+    #[inline]
+    fn push(&mut self, item: T) -> Result<Null, T> {
+        Guard::push(self, item).map_err(PushError::into_inner)
+    }
+}
+
+impl<'c, Sub> private::Sealed for Guard<'c, Sub> where Sub: RingSubmit {}
+
+impl<'c, Sub> RingSubmit for Guard<'c, Sub>
+where
+    Sub: RingSubmit,
+{
+    #[inline]
+    fn ring(&self) -> RingId {
+        self.submitter.ring()
+    }
+}
+
+#[cfg(all(test, feature = "features-checker"))]
+mod tests {
+    use super::*;
+    use crate::{platform::iouring::IoUringProbe, uringio::operator::nop::Nop};
+
+    struct Queue(usize);
+
+    impl<T> Submit<T> for Queue {
+        fn push(&mut self, item: T) -> Result<Null, T> {
+            if self.0 == 0 {
+                return Err(item);
+            }
+            self.0 -= 1;
+            Ok(NULL)
+        }
+    }
+
+    fn capabilities(nop: bool) -> Capabilities {
+        let mut raw = IoUringProbe::default();
+        raw.ops_len = 1;
+        raw.ops[0].flags = nop as u16;
+        Capabilities::from_parts(
+            Probe::from(raw),
+            IoUringFeatureFlags::empty(),
+            IoUringSetupFlags::empty(),
+        )
+    }
+
+    #[test]
+    fn test_guard() {
+        let caps = capabilities(false);
+        let mut guard = caps.guard(Queue(1));
+        assert!(matches!(guard.push(Nop::new()), Err(PushError::Unsupported(_))));
+
+        let caps = capabilities(true);
+        let mut guard = caps.guard(Queue(1));
+        assert!(guard.push(Nop::new()).is_ok());
+        assert!(matches!(guard.push(Nop::new()), Err(PushError::Full(_))));
+    }
+
+    #[test]
+    fn test_guard_entry_opcode() {
+        let caps = capabilities(true);
+        let mut guard = caps.guard(Queue(2));
+
+        // opcode rewritten after build, only IORING_OP_NOP supported
+        let mut nop = Nop::new();
+        nop.opcode = IoUringOp::Nop128;
+        assert_eq!(nop.entry_opcode(), IoUringOp::Nop128 as u8);
+        assert!(matches!(guard.push(nop), Err(PushError::Unsupported(_))));
+
+        let mut sqe = Sqe64::from(Nop::new());
+        assert!(Submit::push(&mut guard, sqe).is_ok());
+        sqe = Sqe64::from(Nop::new());
+        sqe.opcode = IoUringOp::Read;
+        assert!(Submit::push(&mut guard, sqe).is_err());
+    }
+}